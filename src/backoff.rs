@@ -0,0 +1,85 @@
+//! Delays between retries of a failing refresh.
+
+use std::time::Duration;
+
+/// Exponential backoff, the delay growing by `factor` with each consecutive
+/// failure up to `max` and starting over at `base` after a success.
+#[derive(Debug)]
+pub struct Backoff {
+    base: Duration,
+    max: Duration,
+    factor: f64,
+    failures: u32,
+}
+
+impl Backoff {
+    pub fn new(base: Duration, max: Duration, factor: f64) -> Self {
+        Self {
+            base,
+            max,
+            factor,
+            failures: 0,
+        }
+    }
+
+    /// Records a failure, returning the delay before the next attempt.
+    pub fn fail(&mut self) -> Duration {
+        let exponent = i32::try_from(self.failures).unwrap_or(i32::MAX);
+        // An infinite product still ends up at `max`.
+        let secs =
+            (self.base.as_secs_f64() * self.factor.powi(exponent)).min(self.max.as_secs_f64());
+        self.failures = self.failures.saturating_add(1);
+        Duration::from_secs_f64(secs)
+    }
+
+    pub fn succeed(&mut self) {
+        self.failures = 0;
+    }
+
+    /// Failures since the last success.
+    pub fn failures(&self) -> u32 {
+        self.failures
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn backoff() -> Backoff {
+        Backoff::new(Duration::from_secs(15), Duration::from_secs(300), 2.0)
+    }
+
+    #[test]
+    fn grows_exponentially() {
+        let mut backoff = backoff();
+        assert_eq!(backoff.fail(), Duration::from_secs(15));
+        assert_eq!(backoff.fail(), Duration::from_secs(30));
+        assert_eq!(backoff.fail(), Duration::from_secs(60));
+        assert_eq!(backoff.failures(), 3);
+    }
+
+    #[test]
+    fn stops_at_max() {
+        let mut backoff = backoff();
+        for _ in 0..5 {
+            backoff.fail();
+        }
+        assert_eq!(backoff.fail(), Duration::from_secs(300));
+        // Far past where the product overflows to infinity.
+        for _ in 0..2000 {
+            backoff.fail();
+        }
+        assert_eq!(backoff.fail(), Duration::from_secs(300));
+    }
+
+    #[test]
+    fn success_starts_over() {
+        let mut backoff = backoff();
+        backoff.fail();
+        backoff.fail();
+        backoff.succeed();
+        assert_eq!(backoff.failures(), 0);
+        assert_eq!(backoff.fail(), Duration::from_secs(15));
+    }
+}
@@ -0,0 +1,27 @@
+//! Papers of the last refresh kept on disk, shown on launch until the next refresh lands.
+
+use std::{fs, path::Path};
+
+use crate::Paper;
+
+/// Loads the cached papers, none if the cache is absent or corrupt.
+pub fn load(path: &Path) -> Vec<Paper> {
+    match fs::read_to_string(path) {
+        Ok(str) => serde_json::from_str(&str).unwrap_or_else(|err| {
+            tracing::event!(tracing::Level::WARN, "ignoring corrupt paper cache: {err}");
+            vec![]
+        }),
+        Err(_) => vec![],
+    }
+}
+
+/// Writes `papers` to the cache, logging failures.
+pub fn save<'a>(path: &Path, papers: impl Iterator<Item = &'a Paper>) {
+    let papers: Vec<&Paper> = papers.collect();
+    let result = serde_json::to_string(&papers)
+        .map_err(|err| err.to_string())
+        .and_then(|str| fs::write(path, str).map_err(|err| err.to_string()));
+    if let Err(err) = result {
+        tracing::event!(tracing::Level::ERROR, "failed to save paper cache: {err}");
+    }
+}
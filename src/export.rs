@@ -0,0 +1,81 @@
+//! Papers exported to a file, for periodic reporting.
+//!
+//! Files ending in `.json` get an array of papers shaped like the pending
+//! endpoint's response. Any other file gets CSV with a header line.
+
+use std::path::Path;
+
+use chrono::NaiveDate;
+
+use crate::Paper;
+
+/// An inclusive range of local dates, open-ended where a bound is `None`.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct DateRange {
+    pub from: Option<NaiveDate>,
+    pub to: Option<NaiveDate>,
+}
+
+impl DateRange {
+    /// Parses `YYYY-MM-DD` bounds, blank ones are open.
+    pub fn parse(from: &str, to: &str) -> Result<Self, String> {
+        let parse = |str: &str| {
+            let str = str.trim();
+            (!str.is_empty())
+                .then(|| {
+                    NaiveDate::parse_from_str(str, "%Y-%m-%d")
+                        .map_err(|err| format!("invalid date {str:?}: {err}"))
+                })
+                .transpose()
+        };
+        let range = Self {
+            from: parse(from)?,
+            to: parse(to)?,
+        };
+        if let (Some(from), Some(to)) = (range.from, range.to) {
+            if from > to {
+                return Err(format!("{from} is after {to}"));
+            }
+        }
+        Ok(range)
+    }
+
+    pub fn contains(&self, paper: &Paper) -> bool {
+        let date = paper.time.with_timezone(&chrono::Local).date_naive();
+        self.from.is_none_or(|from| from <= date) && self.to.is_none_or(|to| date <= to)
+    }
+}
+
+/// Writes `papers` to `path`, in the given order.
+pub fn write(path: &Path, papers: &[&Paper]) -> Result<(), String> {
+    let str = if path.extension().is_some_and(|ext| ext == "json") {
+        serde_json::to_string_pretty(papers).map_err(|err| err.to_string())?
+    } else {
+        let mut str = String::from("pid,time,name,email,info,color,processed\n");
+        for paper in papers {
+            let fields = [
+                paper.pid.to_string(),
+                paper.time.to_rfc3339(),
+                paper.name.clone(),
+                paper.email.clone().unwrap_or_default(),
+                paper.info.clone(),
+                paper.color.clone().unwrap_or_default(),
+                paper.processed.map(|p| p.to_string()).unwrap_or_default(),
+            ];
+            let fields: Vec<String> = fields.iter().map(|field| csv_field(field)).collect();
+            str.push_str(&fields.join(","));
+            str.push('\n');
+        }
+        str
+    };
+    std::fs::write(path, str).map_err(|err| format!("cannot write {}: {err}", path.display()))
+}
+
+/// Quotes `field` if it holds a separator, a quote or a line break.
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_owned()
+    }
+}
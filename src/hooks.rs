@@ -0,0 +1,115 @@
+//! External commands run on paper events, for integrations that live outside the crate.
+//!
+//! A hook is a program followed by its arguments. The paper is passed in the
+//! environment as `SUBBOARD_EVENT`, `SUBBOARD_BOARD`, `SUBBOARD_PID`,
+//! `SUBBOARD_NAME`, `SUBBOARD_EMAIL`, `SUBBOARD_INFO`, `SUBBOARD_TIME` and
+//! `SUBBOARD_URL`, and its pid as the last argument.
+
+use std::process::Stdio;
+
+use serde::Deserialize;
+
+use crate::Paper;
+
+/// Environment variables passed to hooks, with what they hold.
+pub const VARIABLES: [(&str, &str); 8] = [
+    ("SUBBOARD_EVENT", "accept, reject or new_paper"),
+    ("SUBBOARD_BOARD", "The board's name, if any"),
+    ("SUBBOARD_PID", "The pid, also the last argument"),
+    ("SUBBOARD_NAME", "The author's name"),
+    ("SUBBOARD_EMAIL", "The author's email, if any"),
+    ("SUBBOARD_INFO", "The paper's info"),
+    ("SUBBOARD_TIME", "The submission time in RFC 3339"),
+    ("SUBBOARD_URL", "The paper's page, if any"),
+];
+
+#[derive(Deserialize, Debug, Default)]
+pub struct Hooks {
+    #[serde(default)]
+    on_accept: Option<Vec<String>>,
+    #[serde(default)]
+    on_reject: Option<Vec<String>>,
+    /// Run for pending papers showing up after the first refresh.
+    #[serde(default)]
+    on_new_paper: Option<Vec<String>>,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum Event {
+    Accept,
+    Reject,
+    NewPaper,
+}
+
+impl Event {
+    fn name(self) -> &'static str {
+        match self {
+            Event::Accept => "accept",
+            Event::Reject => "reject",
+            Event::NewPaper => "new_paper",
+        }
+    }
+}
+
+impl Hooks {
+    pub fn validate(&self) -> Result<(), String> {
+        for (key, command) in [
+            ("on_accept", &self.on_accept),
+            ("on_reject", &self.on_reject),
+            ("on_new_paper", &self.on_new_paper),
+        ] {
+            if command.as_ref().is_some_and(|command| command.is_empty()) {
+                return Err(format!("hooks.{key} needs a program to run"));
+            }
+        }
+        Ok(())
+    }
+
+    pub fn has(&self, event: Event) -> bool {
+        self.command(event).is_some()
+    }
+
+    fn command(&self, event: Event) -> Option<&[String]> {
+        match event {
+            Event::Accept => &self.on_accept,
+            Event::Reject => &self.on_reject,
+            Event::NewPaper => &self.on_new_paper,
+        }
+        .as_deref()
+    }
+
+    /// Runs the hook of `event` on `paper` of `board` if one is configured, logging its failures.
+    pub async fn run(&self, event: Event, board: Option<&str>, paper: Paper) {
+        let Some([program, args @ ..]) = self.command(event) else {
+            return;
+        };
+        let status = tokio::process::Command::new(program)
+            .args(args)
+            .arg(paper.pid.to_string())
+            .env("SUBBOARD_EVENT", event.name())
+            .env("SUBBOARD_BOARD", board.unwrap_or_default())
+            .env("SUBBOARD_PID", paper.pid.to_string())
+            .env("SUBBOARD_NAME", &paper.name)
+            .env("SUBBOARD_EMAIL", paper.email.unwrap_or_default())
+            .env("SUBBOARD_INFO", &paper.info)
+            .env("SUBBOARD_TIME", paper.time.to_rfc3339())
+            .env("SUBBOARD_URL", paper.url.unwrap_or_default())
+            .stdin(Stdio::null())
+            .status()
+            .await;
+        match status {
+            Ok(status) if status.success() => (),
+            Ok(status) => tracing::event!(
+                tracing::Level::WARN,
+                "{} hook for paper {} exited with {status}",
+                event.name(),
+                paper.pid
+            ),
+            Err(err) => tracing::event!(
+                tracing::Level::WARN,
+                "cannot run {} hook `{program}`: {err}",
+                event.name()
+            ),
+        }
+    }
+}
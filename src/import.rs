@@ -0,0 +1,123 @@
+//! Decisions imported from a file, for applying offline reviews in bulk.
+//!
+//! Files ending in `.json` hold either an array of `{"pid": 1, "decision": "accept"}`
+//! objects or an object mapping pids to decisions. Any other file is read as CSV
+//! with `pid,decision` lines, optionally preceded by a header.
+
+use std::path::Path;
+
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum Decision {
+    #[serde(alias = "accepted")]
+    Accept,
+    #[serde(alias = "rejected")]
+    Reject,
+}
+
+impl std::str::FromStr for Decision {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "accept" | "accepted" => Ok(Self::Accept),
+            "reject" | "rejected" => Ok(Self::Reject),
+            _ => Err(format!("unknown decision {s:?}")),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum JsonDecisions {
+    List(Vec<JsonDecision>),
+    Map(#[serde(deserialize_with = "deserialize_entries")] Vec<(String, Decision)>),
+}
+
+/// Reads an object as its entries, keeping the order a map would lose.
+fn deserialize_entries<'de, D>(deserializer: D) -> Result<Vec<(String, Decision)>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    struct EntriesVisitor;
+
+    impl<'de> serde::de::Visitor<'de> for EntriesVisitor {
+        type Value = Vec<(String, Decision)>;
+
+        fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+            formatter.write_str("an object mapping pids to decisions")
+        }
+
+        fn visit_map<A: serde::de::MapAccess<'de>>(
+            self,
+            mut map: A,
+        ) -> Result<Self::Value, A::Error> {
+            let mut entries = Vec::with_capacity(map.size_hint().unwrap_or_default());
+            while let Some(entry) = map.next_entry()? {
+                entries.push(entry);
+            }
+            Ok(entries)
+        }
+    }
+
+    deserializer.deserialize_map(EntriesVisitor)
+}
+
+#[derive(Deserialize)]
+struct JsonDecision {
+    #[serde(deserialize_with = "crate::deserialize_pid")]
+    pid: u64,
+    decision: Decision,
+}
+
+/// Reads the decisions of a file, in file order.
+pub fn read(path: &Path) -> Result<Vec<(u64, Decision)>, String> {
+    let str = std::fs::read_to_string(path)
+        .map_err(|err| format!("cannot read {}: {err}", path.display()))?;
+
+    if path.extension().is_some_and(|ext| ext == "json") {
+        return match serde_json::from_str(&str)
+            .map_err(|err| format!("cannot parse {}: {err}", path.display()))?
+        {
+            JsonDecisions::List(list) => {
+                Ok(list.into_iter().map(|e| (e.pid, e.decision)).collect())
+            }
+            JsonDecisions::Map(entries) => entries
+                .into_iter()
+                .map(|(pid, decision)| {
+                    pid.trim()
+                        .parse()
+                        .map(|pid| (pid, decision))
+                        .map_err(|err| format!("invalid pid {pid:?}: {err}"))
+                })
+                .collect(),
+        };
+    }
+
+    let mut decisions = vec![];
+    for (i, line) in str.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        let (pid, decision) = line
+            .split_once(',')
+            .ok_or_else(|| format!("line {}: expected `pid,decision`", i + 1))?;
+        let Ok(pid) = pid.trim().parse() else {
+            if i == 0 {
+                // Header.
+                continue;
+            }
+            return Err(format!("line {}: invalid pid {pid:?}", i + 1));
+        };
+        decisions.push((
+            pid,
+            decision
+                .parse()
+                .map_err(|err| format!("line {}: {err}", i + 1))?,
+        ));
+    }
+    Ok(decisions)
+}
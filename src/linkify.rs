@@ -0,0 +1,98 @@
+//! Plain URLs detected in paper info, for info that is not rendered as markdown.
+
+/// Characters that end a sentence rather than the URL before them.
+const TRAILING: &[char] = &['.', ',', ';', ':', '!', '?', '\'', '"'];
+
+/// The `http` and `https` URLs in `str`, in order.
+///
+/// A URL runs until whitespace or an angle bracket, without trailing
+/// punctuation or closing brackets that have no opening one inside it.
+pub fn links(str: &str) -> Vec<&str> {
+    let mut links = vec![];
+    let mut rest = str;
+    while let Some(start) = find_scheme(rest) {
+        let candidate = &rest[start..];
+        let end = candidate
+            .find(|c: char| c.is_whitespace() || matches!(c, '<' | '>'))
+            .unwrap_or(candidate.len());
+        let link = trim(&candidate[..end]);
+        if !link.ends_with("://") {
+            links.push(link);
+        }
+        rest = &candidate[end..];
+    }
+    links
+}
+
+/// Start of the next scheme that does not continue a word, e.g. not `xhttp://`.
+fn find_scheme(str: &str) -> Option<usize> {
+    let mut from = 0;
+    while let Some(pos) = str[from..].find("http").map(|pos| from + pos) {
+        let tail = &str[pos..];
+        let word_start = !str[..pos]
+            .chars()
+            .next_back()
+            .is_some_and(char::is_alphanumeric);
+        if word_start && (tail.starts_with("http://") || tail.starts_with("https://")) {
+            return Some(pos);
+        }
+        from = pos + "http".len();
+    }
+    None
+}
+
+fn trim(mut link: &str) -> &str {
+    loop {
+        let trimmed = link.trim_end_matches(TRAILING);
+        let trimmed = match trimmed.chars().next_back() {
+            Some(close @ (')' | ']')) => {
+                let open = if close == ')' { '(' } else { '[' };
+                if trimmed.matches(open).count() < trimmed.matches(close).count() {
+                    &trimmed[..trimmed.len() - 1]
+                } else {
+                    trimmed
+                }
+            }
+            _ => trimmed,
+        };
+        if trimmed.len() == link.len() {
+            return link;
+        }
+        link = trimmed;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn drops_trailing_punctuation() {
+        assert_eq!(links("See https://a.example/x."), ["https://a.example/x"]);
+        assert_eq!(links("http://a.example, then"), ["http://a.example"]);
+        assert_eq!(links("go to https://a.example/x)"), ["https://a.example/x"]);
+    }
+
+    #[test]
+    fn keeps_balanced_brackets() {
+        assert_eq!(
+            links("(see https://en.wikipedia.org/wiki/Rust_(language))."),
+            ["https://en.wikipedia.org/wiki/Rust_(language)"]
+        );
+        assert_eq!(links("(https://a.example/x)"), ["https://a.example/x"]);
+    }
+
+    #[test]
+    fn finds_every_link() {
+        assert_eq!(
+            links("https://a.example and <http://b.example/y>, xhttp://c.example"),
+            ["https://a.example", "http://b.example/y"]
+        );
+    }
+
+    #[test]
+    fn ignores_text_without_links() {
+        assert!(links("no links here, not even http or https://").is_empty());
+        assert!(links("").is_empty());
+    }
+}
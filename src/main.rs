@@ -1,48 +1,257 @@
-use std::{collections::HashMap, fs::File, io::Read, str::FromStr, sync::Arc, time::Duration};
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    fs::File,
+    io::Read,
+    panic::AssertUnwindSafe,
+    path::{Path, PathBuf},
+    str::FromStr,
+    sync::Arc,
+    time::{Duration, Instant, SystemTime},
+};
 
-use chrono::DateTime;
+use chrono::{DateTime, NaiveDate};
 
+use backoff::Backoff;
+use export::DateRange;
 use hex_color::HexColor;
+use hooks::Hooks;
 use iced::{
     color,
-    futures::TryFutureExt,
+    futures::{FutureExt, TryFutureExt},
     keyboard::KeyCode,
     theme,
-    widget::{button, container, horizontal_space, vertical_space, Column, Row, Scrollable, Text},
+    widget::{
+        button, container, horizontal_space, scrollable, text_input, tooltip, vertical_space,
+        Column, Row, Scrollable, Text,
+    },
     Application, Color, Command, Font, Length,
 };
-use iced_aw::Split;
-use serde::Deserialize;
+use iced_aw::{
+    menu::{ItemWidth, MenuBar, MenuTree},
+    Modal, Split,
+};
+use import::Decision;
+use metrics::Metrics;
+use mock::{MockBackend, MockConfig};
+use pulldown_cmark::HeadingLevel;
+use search::Query;
+use serde::{Deserialize, Serialize};
+use state::State;
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+mod backoff;
+mod cache;
+mod export;
+mod hooks;
+mod import;
+mod linkify;
+mod markdown;
+mod metrics;
+mod mock;
+mod search;
+mod state;
+mod style;
+mod template;
 
 fn main() -> iced::Result {
     tracing_subscriber::fmt()
         .with_max_level(tracing::Level::INFO)
         .init();
 
-    let config: Config;
+    let args = Args::parse(std::env::args().skip(1));
+    let profile = args.profile.or_else(|| {
+        std::env::var(PROFILE_ENV)
+            .ok()
+            .filter(|name| !name.is_empty())
+    });
+    let flags = match load_config(args.config, profile) {
+        Ok(boards) => Flags::Config(boards),
+        Err(err) => {
+            tracing::event!(tracing::Level::ERROR, "{err}");
+            Flags::Error(err)
+        }
+    };
 
-    {
-        let mut str = String::new();
-        let mut file = File::open("config.toml").expect("configuration file config.toml not found");
-        file.read_to_string(&mut str).unwrap();
-        config = toml::from_str(&str).unwrap();
-    }
+    let default_font = match &flags {
+        Flags::Config(boards) => Font::with_name(boards[0].font.to_owned().leak()),
+        Flags::Error(_) => Font::DEFAULT,
+    };
 
     App::run(iced::Settings {
         window: iced::window::Settings {
             size: (1200, 800),
             ..Default::default()
         },
-        default_font: Font::with_name(config.font.to_owned().leak()),
-        flags: config,
+        default_font,
         default_text_size: 15.0,
-        ..Default::default()
+        exit_on_close_request: false,
+        ..iced::Settings::with_flags(flags)
     })
 }
 
+/// Reads, parses and validates the configuration file at `path`, with the top-level
+/// keys of the named `[[profile]]` replacing those of the file.
+///
+/// Gives one configuration per `[[board]]`, its keys replacing the top-level ones in
+/// turn, or a single unnamed one without boards.
+fn load_config(path: PathBuf, profile: Option<String>) -> Result<Vec<Config>, String> {
+    let mut str = String::new();
+    File::open(&path)
+        .and_then(|mut file| file.read_to_string(&mut str))
+        .map_err(|err| format!("Cannot read configuration file {}: {err}", path.display()))?;
+    let invalid = |err: String| format!("Invalid configuration file {}: {err}", path.display());
+    let mut table: toml::Table = toml::from_str(&str).map_err(|err| invalid(err.to_string()))?;
+
+    let profiles = match table.remove("profile") {
+        None => vec![],
+        Some(toml::Value::Array(profiles)) => profiles,
+        Some(_) => return Err(invalid("profile must be an array of tables".to_owned())),
+    };
+    if let Some(name) = &profile {
+        let mut names = vec![];
+        let mut selected = None;
+        for value in profiles {
+            let toml::Value::Table(mut overrides) = value else {
+                return Err(invalid("profile must be an array of tables".to_owned()));
+            };
+            let Some(toml::Value::String(profile_name)) = overrides.remove("name") else {
+                return Err(invalid("every profile needs a name".to_owned()));
+            };
+            if profile_name == *name {
+                selected = Some(overrides);
+            }
+            names.push(profile_name);
+        }
+        let Some(overrides) = selected else {
+            return Err(format!(
+                "No profile named {name:?} in {}, available: {}",
+                path.display(),
+                if names.is_empty() {
+                    "none".to_owned()
+                } else {
+                    names.join(", ")
+                }
+            ));
+        };
+        table.extend(overrides);
+    }
+
+    let boards = match table.remove("board") {
+        None => vec![],
+        Some(toml::Value::Array(boards)) => boards,
+        Some(_) => return Err(invalid("board must be an array of tables".to_owned())),
+    };
+    let finish = |table: toml::Table, board: Option<String>| {
+        let mut config: Config = toml::Value::Table(table)
+            .try_into()
+            .map_err(|err: toml::de::Error| err.to_string())
+            .and_then(|config: Config| config.validate().map(|()| config))
+            .map_err(|err| match &board {
+                Some(board) => invalid(format!("board {board:?}: {err}")),
+                None => invalid(err),
+            })?;
+        config.path = path.clone();
+        config.profile = profile.clone();
+        config.board = board;
+        Ok(config)
+    };
+    if boards.is_empty() {
+        return Ok(vec![finish(table, None)?]);
+    }
+    let mut names = HashSet::new();
+    boards
+        .into_iter()
+        .map(|value| {
+            let toml::Value::Table(mut overrides) = value else {
+                return Err(invalid("board must be an array of tables".to_owned()));
+            };
+            let Some(toml::Value::String(name)) = overrides.remove("name") else {
+                return Err(invalid("every board needs a name".to_owned()));
+            };
+            // Names key the paper caches.
+            if !names.insert(name.clone()) {
+                return Err(invalid(format!("more than one board is named {name:?}")));
+            }
+            let mut table = table.clone();
+            table.extend(overrides);
+            finish(table, Some(name))
+        })
+        .collect()
+}
+
+/// What the app starts with, a configuration that failed to load shows an error instead.
+#[derive(Debug)]
+enum Flags {
+    /// Configurations of the boards, never empty.
+    Config(Vec<Config>),
+    Error(String),
+}
+
+/// The HTTP client, authenticating with [`Config::api_token`] if set and giving up
+/// after [`Config::request_timeout_secs`].
+fn build_client(config: &Config) -> Result<reqwest::Client, String> {
+    let mut headers = reqwest::header::HeaderMap::new();
+    if let Some(token) = &config.api_token {
+        let mut value = reqwest::header::HeaderValue::from_str(&format!("Bearer {token}"))
+            .map_err(|_| "api_token holds characters not allowed in a header".to_owned())?;
+        value.set_sensitive(true);
+        headers.insert(reqwest::header::AUTHORIZATION, value);
+    }
+    reqwest::Client::builder()
+        .default_headers(headers)
+        .timeout(Duration::from_secs(config.request_timeout_secs))
+        .connect_timeout(Duration::from_secs(config.connect_timeout_secs))
+        .build()
+        .map_err(|err| format!("Cannot create the HTTP client: {err}"))
+}
+
+/// Command line arguments.
+struct Args {
+    /// The configuration file given as the first positional argument or with `--config`,
+    /// `config.toml` otherwise.
+    config: PathBuf,
+    /// Profile given with `--profile`.
+    profile: Option<String>,
+}
+
+impl Args {
+    fn parse(mut args: impl Iterator<Item = String>) -> Self {
+        let mut config = None;
+        let mut profile = None;
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--config" => {
+                    if let Some(path) = args.next() {
+                        config.get_or_insert(path.into());
+                    }
+                }
+                "--profile" => profile = args.next(),
+                _ if !arg.starts_with("--") => {
+                    config.get_or_insert(arg.into());
+                }
+                _ => tracing::event!(tracing::Level::WARN, "ignoring unknown argument {arg}"),
+            }
+        }
+        Self {
+            config: config.unwrap_or_else(|| DEFAULT_CONFIG_PATH.into()),
+            profile,
+        }
+    }
+}
+
 /// Configuration file abstraction.
 #[derive(Deserialize, Debug, Default)]
 struct Config {
+    /// Where the configuration was read from.
+    #[serde(skip)]
+    path: PathBuf,
+    /// The `[[profile]]` applied on top of the file.
+    #[serde(skip)]
+    profile: Option<String>,
+    /// Name of the `[[board]]` applied on top of the file and the profile.
+    #[serde(skip)]
+    board: Option<String>,
+
     host_url: String,
 
     /// `@RequestMapping("xxx")`.
@@ -51,198 +260,2856 @@ struct Config {
     paper_need_process_mapping: String,
     /// `@PostMapping("xxx")`.
     process_paper_mapping: String,
+    /// `@PostMapping("xxx")`, posted with the pid to reject a paper.
+    #[serde(default)]
+    reject_paper_mapping: Option<String>,
+    /// Matching papers above which selecting them all asks for confirmation.
+    #[serde(default = "Config::default_confirm_select_above")]
+    confirm_select_above: usize,
+    /// Canned reasons picked from when rejecting, posted as `reason`.
+    #[serde(default)]
+    reject_reasons: Vec<String>,
+    /// Refuses to reject without a canned or typed reason.
+    #[serde(default)]
+    require_reject_reason: bool,
+    /// JSON body of accept and reject requests in place of the `pid` query, see [`template`].
+    #[serde(default)]
+    decision_body: Option<serde_json::Value>,
+    /// Who makes the decisions, shown in the toolbar and posted as `operator` without a
+    /// [`Config::decision_body`], whose `{operator}` falls back to the profile's name.
+    #[serde(default)]
+    operator: Option<String>,
+    /// External commands run on paper events.
+    #[serde(default)]
+    hooks: Hooks,
+    /// `@PostMapping("xxx")`, posted with the pid whenever a paper is opened.
+    #[serde(default)]
+    view_receipt_mapping: Option<String>,
+    /// `@PostMapping("xxx")`, posted with the pid to undo a decision.
+    #[serde(default)]
+    unprocess_mapping: Option<String>,
+    /// `@GetMapping("xxx")`, queried with `page` and `size` for processed papers.
+    #[serde(default)]
+    history_mapping: Option<String>,
 
     font: String,
+
+    /// Sent as a `Bearer` token with every request.
+    #[serde(default)]
+    api_token: Option<String>,
+    /// JSON pointer to the message in the body of backend errors, e.g. `/error/message`.
+    #[serde(default)]
+    error_message_path: Option<String>,
+
+    /// Refresh failures kept for the error history, 0 to keep none.
+    #[serde(default = "Config::default_error_history_len")]
+    error_history_len: usize,
+
+    /// Seconds to wait before retrying a failed refresh, the first delay of the backoff.
+    #[serde(default = "Config::default_retry_secs")]
+    retry_secs: u64,
+    /// Longest wait between retries, however many failed in a row.
+    #[serde(default = "Config::default_retry_max_secs")]
+    retry_max_secs: u64,
+    /// Growth of the wait with each further failure, `1.0` to retry at a fixed pace.
+    #[serde(default = "Config::default_retry_factor")]
+    retry_factor: f64,
+    /// Seconds a request may take in total before it fails, so a stalled backend
+    /// cannot hold a refresh forever.
+    #[serde(default = "Config::default_request_timeout_secs")]
+    request_timeout_secs: u64,
+    /// Seconds to wait for the connection to the backend.
+    #[serde(default = "Config::default_connect_timeout_secs")]
+    connect_timeout_secs: u64,
+    /// Seconds between refreshes, outside of the [`Config::schedule`].
+    #[serde(default = "Config::default_refresh_interval_secs")]
+    refresh_interval_secs: u64,
+    /// Seconds to wait when a refresh is still in flight as the next one is due.
+    #[serde(default = "Config::default_refresh_backoff_secs")]
+    refresh_backoff_secs: u64,
+    /// Refresh intervals for times of day, `refresh_interval_secs` applies outside of them.
+    #[serde(default)]
+    schedule: Vec<Schedule>,
+    /// Seconds between automatic sweeps of processed papers, `0` to disable.
+    #[serde(default)]
+    auto_clean_interval_secs: u64,
+    /// Refreshes right after accepting, rejecting or undoing.
+    #[serde(default = "Config::default_auto_refresh_after_action")]
+    auto_refresh_after_action: bool,
+    /// Asks the pending endpoint only for papers changed `since` the last
+    /// successful refresh, for backends supporting that parameter.
+    #[serde(default)]
+    delta_refresh: bool,
+    /// Drops papers the pending endpoint returns as already processed.
+    #[serde(default)]
+    ignore_processed_from_backend: bool,
+    /// Marks papers accepted before the server confirms, reverting on failure.
+    #[serde(default)]
+    optimistic_accept: bool,
+    /// Milliseconds a just-decided paper is highlighted in its decision color, `0` to disable.
+    #[serde(default = "Config::default_decision_flash_ms")]
+    decision_flash_ms: u64,
+    /// Holds the decision highlight and the refresh indicator steady instead of animating them.
+    #[serde(default)]
+    reduced_motion: bool,
+    /// Background of the selected rows in the paper list, e.g. `#3B82F6`.
+    #[serde(default)]
+    selection_color: Option<HexColor>,
+    /// Color of accepted papers and the Accept button regardless of the theme, e.g. `#16A34A`.
+    #[serde(default)]
+    accept_color: Option<HexColor>,
+    /// Color of rejected papers and the Reject button regardless of the theme.
+    #[serde(default)]
+    reject_color: Option<HexColor>,
+    /// Keeps papers whose processing the server has not confirmed when cleaning.
+    #[serde(default)]
+    clean_confirmed_only: bool,
+    /// Action run once after the UI initializes.
+    #[serde(default)]
+    on_start: StartAction,
+    /// Shows the pending paper count in the window title, which is what
+    /// taskbars and docks display since winit exposes no badge API.
+    #[serde(default)]
+    pending_badge: bool,
+    /// Text size of the rows in the paper list.
+    #[serde(default = "Config::default_list_font_size")]
+    list_font_size: f32,
+    /// Height of the rows in the paper list.
+    #[serde(default = "Config::default_list_row_height")]
+    list_row_height: f32,
+    /// Paper count above which only the rows around the viewport are built.
+    #[serde(default = "Config::default_virtualize_above")]
+    virtualize_above: usize,
+    /// Serves session counters on `127.0.0.1` at this port.
+    #[serde(default)]
+    metrics_port: Option<u16>,
+    /// Serves papers from a local fixture instead of the network.
+    #[serde(default)]
+    mock: Option<MockConfig>,
+    /// Hours after which a pending paper is flagged stale.
+    #[serde(default)]
+    stale_after_hours: Option<f64>,
+    /// Orders papers by a weighted score instead of newest first.
+    #[serde(default)]
+    ranking: Option<Ranking>,
+    #[serde(default)]
+    time_format: TimeFormat,
+    /// Goes round from the last paper to the first with J and Down, and back with K and Up.
+    #[serde(default)]
+    wrap_navigation: bool,
+    /// Groups the list under submission date headers while it is ordered by time.
+    #[serde(default = "Config::default_group_by_date")]
+    group_by_date: bool,
+    /// Where the Accept button sits in the detail pane.
+    #[serde(default)]
+    action_buttons: ActionButtons,
+    /// Renders the info of papers as markdown, with clickable links.
+    #[serde(default)]
+    render_markdown: bool,
+    /// Sections of the detail pane in display order, omitted ones are hidden.
+    #[serde(default = "Config::default_detail_order")]
+    detail_order: Vec<String>,
+    /// Colors of the light theme.
+    #[serde(default)]
+    theme: Option<ThemeColors>,
+    /// Colors of the dark theme.
+    #[serde(default)]
+    dark_theme: Option<ThemeColors>,
+}
+
+/// The `[theme]` and `[dark_theme]` tables, colors replacing those of the built-in palette.
+#[derive(Deserialize, Debug, Default)]
+struct ThemeColors {
+    #[serde(default)]
+    background: Option<HexColor>,
+    #[serde(default)]
+    text: Option<HexColor>,
+    #[serde(default)]
+    primary: Option<HexColor>,
+    #[serde(default)]
+    success: Option<HexColor>,
+    #[serde(default)]
+    danger: Option<HexColor>,
+}
+
+impl ThemeColors {
+    /// `base` with the configured colors, or `base` itself without a table.
+    fn theme(colors: Option<&Self>, base: iced::Theme) -> iced::Theme {
+        let Some(colors) = colors else {
+            return base;
+        };
+        let mut palette = base.palette();
+        for (color, field) in [
+            (colors.background, &mut palette.background),
+            (colors.text, &mut palette.text),
+            (colors.primary, &mut palette.primary),
+            (colors.success, &mut palette.success),
+            (colors.danger, &mut palette.danger),
+        ] {
+            if let Some(color) = color {
+                *field = style::from_hex(color);
+            }
+        }
+        iced::Theme::custom(palette)
+    }
+}
+
+/// A section of the detail pane, see [`Config::detail_order`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DetailSection {
+    Info,
+    Attachments,
+    Name,
+    Email,
+    Time,
+}
+
+impl FromStr for DetailSection {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "info" => Ok(Self::Info),
+            "attachments" => Ok(Self::Attachments),
+            "name" => Ok(Self::Name),
+            "email" => Ok(Self::Email),
+            "time" => Ok(Self::Time),
+            _ => Err(format!("unknown detail section {s:?}")),
+        }
+    }
+}
+
+/// How submission times are displayed.
+#[derive(Deserialize, Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+enum TimeFormat {
+    /// `Tue, 1 Jul 2003 10:52:37 +0000`.
+    #[default]
+    Rfc2822,
+    /// ISO-8601 with an explicit offset, `2003-07-01T10:52:37+00:00`.
+    Rfc3339,
+}
+
+impl TimeFormat {
+    fn format(self, time: &DateTime<chrono::Utc>) -> String {
+        match self {
+            TimeFormat::Rfc2822 => time.to_rfc2822(),
+            TimeFormat::Rfc3339 => time.to_rfc3339_opts(chrono::SecondsFormat::Secs, false),
+        }
+    }
+
+    /// Formats a day of the list's date headers, naming today and yesterday.
+    fn format_date(self, date: NaiveDate) -> String {
+        let today = chrono::Local::now().date_naive();
+        if date == today {
+            "Today".to_owned()
+        } else if today.pred_opt() == Some(date) {
+            "Yesterday".to_owned()
+        } else {
+            match self {
+                TimeFormat::Rfc2822 => date.format("%a, %-d %b %Y").to_string(),
+                TimeFormat::Rfc3339 => date.format("%Y-%m-%d").to_string(),
+            }
+        }
+    }
+}
+
+/// A row of the paper list, a paper by its index among the visible ones or a date header.
+enum ListRow {
+    Header(NaiveDate),
+    Paper(usize),
+}
+
+/// Field the paper list is ordered by.
+#[derive(Deserialize, Serialize, Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+enum SortKey {
+    #[default]
+    Time,
+    Name,
+    Pid,
+}
+
+impl SortKey {
+    fn label(self) -> &'static str {
+        match self {
+            SortKey::Time => "Time",
+            SortKey::Name => "Name",
+            SortKey::Pid => "Pid",
+        }
+    }
+
+    /// Direction the key is first applied in, newest first for times.
+    fn ascending_first(self) -> bool {
+        self != SortKey::Time
+    }
+
+    fn next(self) -> Self {
+        match self {
+            SortKey::Time => SortKey::Name,
+            SortKey::Name => SortKey::Pid,
+            SortKey::Pid => SortKey::Time,
+        }
+    }
+}
+
+/// Papers shown in the list by their processing state.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+enum Filter {
+    #[default]
+    All,
+    Pending,
+    Processed,
+}
+
+impl Filter {
+    fn matches(self, paper: &Paper) -> bool {
+        match self {
+            Filter::All => true,
+            Filter::Pending => paper.processed.is_none(),
+            Filter::Processed => paper.processed.is_some(),
+        }
+    }
+
+    fn next(self) -> Self {
+        match self {
+            Filter::All => Filter::Pending,
+            Filter::Pending => Filter::Processed,
+            Filter::Processed => Filter::All,
+        }
+    }
+
+    /// Menu label, naming the filter a click switches to.
+    fn next_label(self) -> &'static str {
+        match self.next() {
+            Filter::All => "Show all papers",
+            Filter::Pending => "Pending papers only",
+            Filter::Processed => "Processed papers only",
+        }
+    }
+}
+
+/// The `[ranking]` table, weights of the signals combined by [`Ranking::score`].
+#[derive(Deserialize, Debug, Default)]
+struct Ranking {
+    /// Weight of the paper's `priority` field.
+    #[serde(default)]
+    priority: f64,
+    /// Weight per hour since submission.
+    #[serde(default)]
+    age_hours: f64,
+    /// Bonus per paper color, keyed by hex color like `#ff0000`.
+    #[serde(default)]
+    colors: HashMap<String, f64>,
+}
+
+impl Ranking {
+    /// Higher scores are shown first.
+    fn score(&self, paper: &Paper, now: DateTime<chrono::Utc>) -> f64 {
+        let age = (now - paper.time).num_seconds() as f64 / 3600.0;
+        let color = paper
+            .color
+            .as_deref()
+            .and_then(|color| {
+                self.colors
+                    .iter()
+                    .find(|(key, _)| key.eq_ignore_ascii_case(color))
+                    .map(|(_, bonus)| *bonus)
+            })
+            .unwrap_or_default();
+        self.priority * paper.priority.unwrap_or_default() + self.age_hours * age + color
+    }
+}
+
+/// See [`Config::action_buttons`], either way the buttons stay clear of the scrolling details.
+#[derive(Deserialize, Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+enum ActionButtons {
+    Top,
+    #[default]
+    Bottom,
+}
+
+/// A `[[schedule]]` entry of the configuration file.
+#[derive(Deserialize, Debug)]
+struct Schedule {
+    /// Local time the range starts at, e.g. `"09:00"`.
+    from: chrono::NaiveTime,
+    /// Local time the range ends at, before `from` for ranges spanning midnight.
+    to: chrono::NaiveTime,
+    interval_secs: u64,
+}
+
+impl Schedule {
+    fn contains(&self, time: chrono::NaiveTime) -> bool {
+        if self.from <= self.to {
+            self.from <= time && time < self.to
+        } else {
+            self.from <= time || time < self.to
+        }
+    }
+}
+
+/// See [`Config::on_start`].
+#[derive(Deserialize, Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+enum StartAction {
+    /// Only the regular initial refresh.
+    #[default]
+    Refresh,
+    /// Open the newest paper once papers are loaded.
+    SelectNewest,
+    /// Start in focus mode, showing only the open paper.
+    FocusMode,
+}
+
+impl Config {
+    #[inline]
+    fn default_error_history_len() -> usize {
+        20
+    }
+
+    #[inline]
+    fn default_confirm_select_above() -> usize {
+        20
+    }
+
+    #[inline]
+    fn default_decision_flash_ms() -> u64 {
+        800
+    }
+
+    #[inline]
+    fn default_retry_secs() -> u64 {
+        15
+    }
+
+    #[inline]
+    fn default_retry_max_secs() -> u64 {
+        300
+    }
+
+    #[inline]
+    fn default_retry_factor() -> f64 {
+        2.0
+    }
+
+    #[inline]
+    fn default_request_timeout_secs() -> u64 {
+        30
+    }
+
+    #[inline]
+    fn default_connect_timeout_secs() -> u64 {
+        10
+    }
+
+    #[inline]
+    fn default_refresh_interval_secs() -> u64 {
+        45
+    }
+
+    #[inline]
+    fn default_refresh_backoff_secs() -> u64 {
+        30
+    }
+
+    #[inline]
+    fn default_auto_refresh_after_action() -> bool {
+        true
+    }
+
+    #[inline]
+    fn default_group_by_date() -> bool {
+        true
+    }
+
+    fn default_detail_order() -> Vec<String> {
+        ["info", "attachments", "name", "email", "time"]
+            .into_iter()
+            .map(str::to_owned)
+            .collect()
+    }
+
+    #[inline]
+    fn default_list_font_size() -> f32 {
+        15.0
+    }
+
+    #[inline]
+    fn default_list_row_height() -> f32 {
+        18.5
+    }
+
+    #[inline]
+    fn default_virtualize_above() -> usize {
+        200
+    }
+
+    /// Interval of the refresh loop at local `time`, from the first matching [`Schedule`].
+    fn refresh_interval(&self, time: chrono::NaiveTime) -> Duration {
+        let secs = self
+            .schedule
+            .iter()
+            .find(|range| range.contains(time))
+            .map_or(self.refresh_interval_secs, |range| range.interval_secs);
+        Duration::from_secs(secs)
+    }
+
+    /// The configured operator, nobody without one.
+    fn operator(&self) -> Option<&str> {
+        self.operator.as_deref()
+    }
+
+    /// Name of the board, empty without boards.
+    fn board_name(&self) -> &str {
+        self.board.as_deref().unwrap_or_default()
+    }
+
+    /// Paper cache next to the state file, e.g. `state.papers.json`, or
+    /// `state.<board>.papers.json` for a board.
+    fn cache_path(&self) -> PathBuf {
+        self.state_path().with_extension(match &self.board {
+            Some(board) => format!("{board}.papers.json"),
+            None => "papers.json".to_owned(),
+        })
+    }
+
+    /// Local state file, kept apart for each configuration file and profile so boards
+    /// and operators do not share it.
+    fn state_path(&self) -> PathBuf {
+        match (&self.profile, self.path == Path::new(DEFAULT_CONFIG_PATH)) {
+            (None, true) => STATE_PATH.into(),
+            (None, false) => self.path.with_extension("state.toml"),
+            (Some(profile), true) => format!("state.{profile}.toml").into(),
+            (Some(profile), false) => self.path.with_extension(format!("{profile}.state.toml")),
+        }
+    }
+
+    /// Url of `mapping` under the global mapping, leaving out empty parts instead of
+    /// producing a stray or doubled slash.
+    fn endpoint(&self, mapping: &str) -> String {
+        let mut url = format!("{}{}", self.host_url, self.global_mapping);
+        let mapping = mapping.trim().trim_start_matches('/');
+        if !mapping.is_empty() {
+            if !url.ends_with('/') {
+                url.push('/');
+            }
+            url.push_str(mapping);
+        }
+        url
+    }
+
+    /// Url of an optional mapping, an empty one counting as unset.
+    fn optional_endpoint(&self, mapping: &Option<String>) -> Option<String> {
+        mapping
+            .as_deref()
+            .filter(|mapping| !mapping.trim().is_empty())
+            .map(|mapping| self.endpoint(mapping))
+    }
+
+    /// Checks values that deserialize fine but make no sense.
+    fn validate(&self) -> Result<(), String> {
+        // Not fatal, a backend might really nest the prefix, but usually a 404.
+        let global = self.global_mapping.trim_matches('/');
+        for (name, mapping) in [
+            (
+                "paper_need_process_mapping",
+                Some(&self.paper_need_process_mapping),
+            ),
+            ("process_paper_mapping", Some(&self.process_paper_mapping)),
+            ("reject_paper_mapping", self.reject_paper_mapping.as_ref()),
+            ("view_receipt_mapping", self.view_receipt_mapping.as_ref()),
+            ("unprocess_mapping", self.unprocess_mapping.as_ref()),
+            ("history_mapping", self.history_mapping.as_ref()),
+        ] {
+            let Some(mapping) = mapping.map(|mapping| mapping.trim().trim_start_matches('/'))
+            else {
+                continue;
+            };
+            if !global.is_empty()
+                && mapping
+                    .strip_prefix(global)
+                    .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+            {
+                tracing::event!(
+                    tracing::Level::WARN,
+                    "{name} {mapping:?} already starts with global_mapping {global:?}, \
+                     requests go to {}",
+                    self.endpoint(mapping)
+                );
+            }
+        }
+        if let Some(body) = &self.decision_body {
+            template::validate(body).map_err(|err| format!("decision_body: {err}"))?;
+        }
+        self.hooks.validate()?;
+        // The mock backend never builds urls.
+        if self.mock.is_none() {
+            for (name, mapping) in [
+                (
+                    "paper_need_process_mapping",
+                    &self.paper_need_process_mapping,
+                ),
+                ("process_paper_mapping", &self.process_paper_mapping),
+            ] {
+                if mapping.trim().is_empty() {
+                    return Err(format!("{name} must not be empty"));
+                }
+            }
+        }
+        if self.refresh_interval_secs == 0 {
+            return Err("refresh_interval_secs must be positive".to_owned());
+        }
+        if self.refresh_backoff_secs == 0 {
+            return Err("refresh_backoff_secs must be positive".to_owned());
+        }
+        if self.request_timeout_secs == 0 || self.connect_timeout_secs == 0 {
+            return Err(
+                "request_timeout_secs and connect_timeout_secs must be positive".to_owned(),
+            );
+        }
+        if self.retry_max_secs < self.retry_secs {
+            return Err(format!(
+                "retry_max_secs must be at least retry_secs ({}), got {}",
+                self.retry_secs, self.retry_max_secs
+            ));
+        }
+        if !(self.retry_factor >= 1.0 && self.retry_factor.is_finite()) {
+            return Err(format!(
+                "retry_factor must be at least 1, got {}",
+                self.retry_factor
+            ));
+        }
+        if !(8.0..=40.0).contains(&self.list_font_size) {
+            return Err(format!(
+                "list_font_size must be between 8 and 40, got {}",
+                self.list_font_size
+            ));
+        }
+        if !(10.0..=80.0).contains(&self.list_row_height) {
+            return Err(format!(
+                "list_row_height must be between 10 and 80, got {}",
+                self.list_row_height
+            ));
+        }
+        if let Some(range) = self.schedule.iter().find(|range| range.interval_secs == 0) {
+            return Err(format!(
+                "schedule interval_secs must be positive, got 0 for {}-{}",
+                range.from, range.to
+            ));
+        }
+        Ok(())
+    }
 }
 
+const NERD_FONT: &[u8] = include_bytes!("../fonts/SymbolsNerdFontMono-Regular.ttf");
+/// Family `NERD_FONT` registers under, which glyphs are rendered with.
+const NERD_FONT_FAMILY: &str = "Symbols Nerd Font Mono";
+
+/// Delay before a view receipt is posted, so rapid J/K navigation
+/// only reports the paper the operator actually stopped on.
+const VIEW_RECEIPT_DEBOUNCE: Duration = Duration::from_millis(800);
+
+/// Interface zoom bounds and the change of each Ctrl+= or Ctrl+- press.
+const TEXT_SCALE_RANGE: std::ops::RangeInclusive<f32> = 0.7..=2.0;
+const TEXT_SCALE_STEP: f32 = 0.1;
+
+/// Time between frames of the fading decision highlight.
+const FLASH_FRAME: Duration = Duration::from_millis(33);
+/// Time between frames of the pulsing refresh indicator.
+const PULSE_FRAME: Duration = Duration::from_millis(100);
+
+/// Oversleeping the refresh loop by this much means the machine was suspended.
+const RESUME_GAP: Duration = Duration::from_secs(60);
+
+/// Decisions posted at once by an import.
+const IMPORT_CONCURRENCY: usize = 4;
+
+/// Processed papers fetched per page of the history.
+const HISTORY_PAGE_SIZE: usize = 50;
+
+/// Number of decisions kept for undo.
+const UNDO_DEPTH: usize = 20;
+
+const DEFAULT_CONFIG_PATH: &str = "config.toml";
+/// Environment variable naming the profile when `--profile` is not given.
+const PROFILE_ENV: &str = "SUBBOARD_PROFILE";
+/// Written by Ctrl+Shift+D for bug reports.
+const DEBUG_DUMP_PATH: &str = "subboard-debug.json";
+/// Local state file of the default configuration, see [`State`].
+const STATE_PATH: &str = "state.toml";
+
+/// Delay before layout changes are saved, so dragging the divider writes once.
+const LAYOUT_SAVE_DEBOUNCE: Duration = Duration::from_millis(500);
+
+const SEARCH_INPUT_ID: &str = "search";
+const REJECT_NOTE_INPUT_ID: &str = "reject_note";
+const DETAIL_SCROLLABLE_ID: &str = "detail";
+
+/// Keyboard bindings listed by the help overlay.
+const SHORTCUTS: [(&str, &str); 20] = [
+    ("Up / K, Down / J", "Open the previous or next paper"),
+    ("Shift+Up, Shift+Down", "Extend the selection"),
+    (
+        "N, Shift+N",
+        "Open the next or previous pending paper, going round",
+    ),
+    ("Enter", "Accept the open or selected papers"),
+    ("Backspace", "Reject the open or selected papers"),
+    ("1 - 9", "Pick a reject reason"),
+    ("O", "Open the paper's page"),
+    ("B", "Block this paper"),
+    ("C", "Clean accepted papers"),
+    ("R", "Refresh"),
+    ("D", "Toggle dark mode"),
+    ("S", "Switch layout"),
+    ("F", "Toggle focus mode, showing only the open paper"),
+    ("Escape", "Close the overlay or the open paper"),
+    ("Ctrl+Z", "Undo the last decision"),
+    ("Ctrl+= / Ctrl+-", "Zoom in or out"),
+    ("Ctrl+0", "Reset the zoom"),
+    ("Tab, Shift+Tab", "Move the focus"),
+    ("Ctrl+Shift+D", "Dump the state for bug reports"),
+    ("?", "Show this help"),
+];
+
+/// Vertical padding around a row of the paper list, on top of [`Config::list_row_height`].
+const LIST_ROW_PADDING: f32 = 10.0;
+/// Room taken by the color swatch, expand toggle, badges and status icon of a list row.
+const LIST_ROW_DECORATION_WIDTH: f32 = 100.0;
+/// Side of the color swatch of a list row.
+const SWATCH_SIZE: f32 = 8.0;
+/// Average advance of a narrow character relative to the font size, for fitting text.
+const AVERAGE_CHAR_WIDTH: f32 = 0.6;
+/// Rows built beyond each edge of the viewport when the list is windowed.
+const OVERSCAN: usize = 10;
+
+/// Width of a nerd-font button in the top bar.
+const ICON_BUTTON_WIDTH: f32 = 23.5;
+/// Room kept for the `PAPERS` label and the paper counts before buttons overflow into a menu.
+const BAR_LABEL_WIDTH: f32 = 200.0;
+/// Width of the sort button in the top bar.
+const SORT_BUTTON_WIDTH: f32 = 60.0;
+
 #[derive(Debug)]
 struct BuiltHost {
     paper_need_process: String,
     process_paper: String,
+    reject_paper: Option<String>,
+    view_receipt: Option<String>,
+    unprocess: Option<String>,
+    history: Option<String>,
 }
 
 #[derive(Debug)]
 struct StaticIns {
     host: BuiltHost,
     client: reqwest::Client,
+    metrics: &'static Metrics,
+    /// Replaces the network when `[mock]` is configured.
+    mock: Option<MockBackend>,
+    /// Parsed [`Config::detail_order`].
+    detail_sections: Vec<DetailSection>,
+    /// Built-in themes with the colors of [`Config::theme`] and [`Config::dark_theme`].
+    light_theme: iced::Theme,
+    dark_theme: iced::Theme,
+    config: Config,
 }
 
-#[derive(Debug)]
-struct App {
-    /// Loaded papers.
-    papers: HashMap<u64, Paper>,
-    static_ins: &'static StaticIns,
+impl StaticIns {
+    /// What requests to the board of `config` need, with the error keeping it from
+    /// working if any.
+    fn build(config: Config, metrics: &'static Metrics) -> (Self, Option<String>) {
+        let span = tracing::span!(tracing::Level::INFO, "board", name = config.board_name());
+        let _span = span.enter();
+
+        let mut error = None;
+        let mock = config
+            .mock
+            .as_ref()
+            .map(MockBackend::load)
+            .transpose()
+            .unwrap_or_else(|err| {
+                error = Some(format!("Invalid mock fixture: {err}"));
+                None
+            });
+        let client = build_client(&config).unwrap_or_else(|err| {
+            error = Some(err);
+            reqwest::Client::new()
+        });
+        let host = BuiltHost {
+            paper_need_process: config.endpoint(&config.paper_need_process_mapping),
+            process_paper: config.endpoint(&config.process_paper_mapping),
+            reject_paper: config.optional_endpoint(&config.reject_paper_mapping),
+            view_receipt: config.optional_endpoint(&config.view_receipt_mapping),
+            unprocess: config.optional_endpoint(&config.unprocess_mapping),
+            history: config.optional_endpoint(&config.history_mapping),
+        };
+        if error.is_none() && mock.is_none() {
+            // Lets operators check the urls against the backend's routes.
+            tracing::event!(
+                tracing::Level::INFO,
+                "pending papers at {}, accepting at {}",
+                redact_url(&host.paper_need_process),
+                redact_url(&host.process_paper)
+            );
+            for (name, url) in [
+                ("rejecting", &host.reject_paper),
+                ("view receipts", &host.view_receipt),
+                ("undoing", &host.unprocess),
+                ("history", &host.history),
+            ] {
+                if let Some(url) = url {
+                    tracing::event!(tracing::Level::INFO, "{name} at {}", redact_url(url));
+                }
+            }
+        }
+        (
+            Self {
+                host,
+                client,
+                metrics,
+                mock,
+                detail_sections: config
+                    .detail_order
+                    .iter()
+                    .filter_map(|key| {
+                        key.parse()
+                            .map_err(|err| tracing::event!(tracing::Level::WARN, "{err}"))
+                            .ok()
+                    })
+                    .collect(),
+                light_theme: ThemeColors::theme(config.theme.as_ref(), iced::Theme::Light),
+                dark_theme: ThemeColors::theme(config.dark_theme.as_ref(), iced::Theme::Dark),
+                config,
+            },
+            error,
+        )
+    }
+}
+
+/// State of one board, shown while it is the [`App::active_board`].
+#[derive(Debug)]
+struct Board {
+    static_ins: &'static StaticIns,
+    /// Loaded papers.
+    papers: HashMap<u64, Paper>,
+    /// Pids of `papers` in display order.
+    order: Vec<u64>,
+    selected_paper: Option<u64>,
+    related_papers: (Option<u64>, Option<u64>),
+    /// Papers picked for a bulk action, in addition to `selected_paper`.
+    multi_selected: HashSet<u64>,
+    /// Moving end of a Shift+J/K range anchored at `selected_paper`.
+    range_cursor: Option<u64>,
+
+    refresh_count: Arc<()>,
+    /// Set when a refresh is asked for while one is in flight.
+    refresh_requested: bool,
+    /// Set when `SelectNewest` ran before any paper was loaded.
+    select_newest_pending: bool,
+    /// Start of the last successful refresh, the `since` of the next delta refresh.
+    last_refresh: Option<DateTime<chrono::Utc>>,
+    /// Start of the refresh in flight.
+    refresh_started: Option<DateTime<chrono::Utc>>,
+    /// When the next retry of a failed refresh is due.
+    retry_at: Option<Instant>,
+    retry_backoff: Backoff,
+    history: HistoryState,
+
+    /// Error shown in a dismissible banner.
+    last_error: Option<String>,
+    /// Banner of the last failed refresh, cleared by the next successful one.
+    refresh_error: Option<String>,
+    /// Recent refresh failures, oldest first, kept after the banner is cleared.
+    error_history: VecDeque<(DateTime<chrono::Utc>, String)>,
+
+    /// Papers waiting for a reject reason to be picked.
+    reject_pending: Vec<u64>,
+    /// Snapshots of recently processed papers, most recent last.
+    undo_stack: VecDeque<Paper>,
+    /// Processed papers put back into review locally, until decided again.
+    re_reviewing: HashSet<u64>,
+    /// Papers whose full info is previewed inline in the list.
+    expanded: HashSet<u64>,
+    /// Whether a refresh succeeded yet, papers only count as new after the first.
+    loaded: bool,
+    /// Accepts of `AcceptAllVisible` still in flight.
+    accept_batch: Option<AcceptBatch>,
+    /// When papers were decided, while their decision is highlighted.
+    flashes: HashMap<u64, Instant>,
+}
+
+/// Accepts started together by `AcceptAllVisible`, reported once all of them returned.
+#[derive(Debug, Default)]
+struct AcceptBatch {
+    pending: HashSet<u64>,
+    total: usize,
+    failed: Vec<u64>,
+}
+
+impl Board {
+    /// A board showing its cached papers, less the blocked ones, until it is refreshed.
+    fn new(static_ins: &'static StaticIns, state: &State, broken: bool) -> Self {
+        let config = &static_ins.config;
+        // Only the network is worth caching, fixtures load instantly anyway.
+        let papers = if broken || static_ins.mock.is_some() {
+            HashMap::new()
+        } else {
+            cache::load(&config.cache_path())
+                .into_iter()
+                .filter(|paper| !state.is_blocked(config.board_name(), paper.pid))
+                .map(|paper| {
+                    (
+                        paper.pid,
+                        Paper {
+                            confirmed: paper.processed.is_some(),
+                            ..paper
+                        },
+                    )
+                })
+                .collect()
+        };
+        Self {
+            static_ins,
+            papers,
+            order: vec![],
+            selected_paper: None,
+            related_papers: (None, None),
+            multi_selected: HashSet::new(),
+            range_cursor: None,
+            refresh_count: Arc::new(()),
+            refresh_requested: false,
+            select_newest_pending: false,
+            last_refresh: None,
+            refresh_started: None,
+            retry_at: None,
+            retry_backoff: Backoff::new(
+                Duration::from_secs(config.retry_secs),
+                Duration::from_secs(config.retry_max_secs),
+                config.retry_factor,
+            ),
+            history: HistoryState::default(),
+            last_error: None,
+            refresh_error: None,
+            error_history: VecDeque::new(),
+            reject_pending: vec![],
+            undo_stack: VecDeque::with_capacity(UNDO_DEPTH),
+            re_reviewing: HashSet::new(),
+            expanded: HashSet::new(),
+            loaded: false,
+            accept_batch: None,
+            flashes: HashMap::new(),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct App {
+    /// Boards by index, never empty.
+    boards: Vec<Board>,
+    /// Index of the board shown and the one messages act on.
+    active_board: usize,
+
+    sort_key: SortKey,
+    sort_ascending: bool,
+
+    split_0_pos: Option<u16>,
+    nerd_font: Font,
+    dark_mode: bool,
+    split_axis: iced_aw::split::Axis,
+    display_bg: bool,
+    /// Hides the paper list, leaving the open paper, toggled with F.
+    focus_mode: bool,
+
+    window_size: (u32, u32),
+    /// Zoom of the whole interface, changed with Ctrl+= and Ctrl+-.
+    text_scale: f32,
+    /// Scroll position of the paper list, for windowed rendering.
+    list_offset: f32,
+    /// Set once the window is closing.
+    closing: bool,
+
+    state: State,
+    /// Count of layout changes, the last saved one in `saved_layout`.
+    layout_changes: u64,
+    saved_layout: u64,
+    /// Modal shown above the main view.
+    overlay: Option<Overlay>,
+    /// Reason typed in the reject prompt.
+    reject_note: String,
+    import: ImportState,
+    export: ExportState,
+
+    search_query: String,
+    search: Query,
+    /// Hides papers that are not stale.
+    stale_only: bool,
+    filter: Filter,
+
+    /// Decisions made since launch or the last reset, for shift handoffs.
+    accepted_this_session: u32,
+    rejected_this_session: u32,
+
+    /// Why the configuration failed to load, the only thing shown if set.
+    config_error: Option<String>,
+}
+
+impl App {
+    fn board(&self) -> &Board {
+        &self.boards[self.active_board]
+    }
+
+    fn board_mut(&mut self) -> &mut Board {
+        &mut self.boards[self.active_board]
+    }
+
+    /// Tabs of the boards, with their pending papers and whether their refresh fails.
+    fn tabs_view(&self) -> iced::Element<'_, Msg, iced::Renderer<iced::Theme>> {
+        let mut tabs = Row::new().spacing(5).padding([5, 10, 0, 10]);
+        for (index, board) in self.boards.iter().enumerate() {
+            let name = board.static_ins.config.board_name();
+            let (pending, unseen) = board
+                .papers
+                .values()
+                .filter(|e| e.processed.is_none())
+                .fold((0, 0), |(pending, unseen), e| {
+                    (
+                        pending + 1,
+                        unseen + usize::from(!self.state.is_seen(name, e.pid)),
+                    )
+                });
+            let mut label = Row::new()
+                .spacing(5)
+                .align_items(iced::Alignment::Center)
+                .push(Text::new(name).size(13.5));
+            if pending > 0 {
+                label = label.push(
+                    Text::new(pending.to_string())
+                        .size(12)
+                        .style(Color::new(0.5, 0.5, 0.5, 1.0)),
+                );
+            }
+            if unseen > 0 {
+                label = label.push(
+                    Text::new(format!("{unseen} new"))
+                        .size(12)
+                        .style(self.theme().palette().primary),
+                );
+            }
+            if board.refresh_error.is_some() {
+                label = label.push(
+                    Text::new("")
+                        .size(12)
+                        .font(self.nerd_font)
+                        .style(self.theme().palette().danger),
+                );
+            }
+            tabs = tabs.push(
+                button(label)
+                    .padding([4, 10])
+                    .style(if index == self.active_board {
+                        theme::Button::Secondary
+                    } else {
+                        theme::Button::Text
+                    })
+                    .on_press(Msg::SwitchBoard(index)),
+            );
+        }
+        Scrollable::new(tabs)
+            .direction(scrollable::Direction::Horizontal(
+                scrollable::Properties::new().width(2).scroller_width(2),
+            ))
+            .into()
+    }
+
+    /// Papers matching the search query in display order, newest first.
+    fn visible_papers(&self) -> Vec<&Paper> {
+        self.board()
+            .order
+            .iter()
+            .filter_map(|pid| self.board().papers.get(pid))
+            .filter(|paper| self.search.matches(paper))
+            .filter(|paper| !self.stale_only || self.is_stale(paper))
+            .filter(|paper| self.filter.matches(paper))
+            .collect()
+    }
+
+    /// Whether a refresh is in flight, holding a clone of `refresh_count`.
+    fn refreshing(&self) -> bool {
+        Arc::strong_count(&self.board().refresh_count) > 1
+    }
+
+    /// Whether the search or a filter hides papers from the list.
+    fn filtering(&self) -> bool {
+        self.filter != Filter::All || self.stale_only || !self.search_query.trim().is_empty()
+    }
+
+    /// Workload shown next to the `PAPERS` label, or how much of it passes the filters.
+    fn paper_counts(&self) -> String {
+        let total = self.board().papers.len();
+        if self.filtering() {
+            format!("showing {} of {total}", self.visible_papers().len())
+        } else {
+            let pending = self
+                .board()
+                .papers
+                .values()
+                .filter(|e| e.processed.is_none())
+                .count();
+            format!("{pending} pending / {total} total")
+        }
+    }
+
+    /// Whether the list is grouped under date headers, only while it is ordered by time.
+    fn groups_by_date(&self) -> bool {
+        self.board().static_ins.config.group_by_date
+            && self.sort_key == SortKey::Time
+            && (self.board().static_ins.config.ranking.is_none() || self.sort_ascending)
+    }
+
+    /// Whether `paper` has been pending longer than [`Config::stale_after_hours`].
+    fn is_stale(&self, paper: &Paper) -> bool {
+        self.board()
+            .static_ins
+            .config
+            .stale_after_hours
+            .is_some_and(|hours| {
+                paper.processed.is_none()
+                    && (chrono::Utc::now() - paper.time).num_seconds() as f64 / 3600.0 > hours
+            })
+    }
+
+    /// Rebuilds `order`, to be called whenever `papers` gains or loses entries.
+    fn resort(&mut self) {
+        let mut papers: Vec<&Paper> = self.board().papers.values().collect();
+        match self.sort_key {
+            SortKey::Time => papers.sort_unstable_by_key(|paper| &paper.time),
+            SortKey::Name => {
+                papers.sort_unstable_by(|a, b| a.name.cmp(&b.name).then(a.pid.cmp(&b.pid)))
+            }
+            SortKey::Pid => papers.sort_unstable_by_key(|paper| paper.pid),
+        }
+        if !self.sort_ascending {
+            papers.reverse();
+        }
+        // The ranking replaces the default order only, a picked sort wins over it.
+        if let Some(ranking) = self
+            .board()
+            .static_ins
+            .config
+            .ranking
+            .as_ref()
+            .filter(|_| self.sort_key == SortKey::Time && !self.sort_ascending)
+        {
+            let now = chrono::Utc::now();
+            // Stable, so equal scores stay newest first.
+            papers.sort_by(|a, b| ranking.score(b, now).total_cmp(&ranking.score(a, now)));
+        }
+        self.board_mut().order = papers.into_iter().map(|paper| paper.pid).collect();
+        self.sync_backlog();
+    }
+
+    /// Updates the backlog metric, to be called whenever papers are added, removed or processed.
+    fn sync_backlog(&self) {
+        let backlog = self
+            .boards
+            .iter()
+            .flat_map(|board| board.papers.values())
+            .filter(|e| e.processed.is_none())
+            .count();
+        self.board()
+            .static_ins
+            .metrics
+            .backlog
+            .store(backlog as u64, std::sync::atomic::Ordering::Relaxed);
+    }
+
+    /// Builds the `OpenPaper` message for `target` with its neighbours in display order.
+    fn open_paper(&self, target: u64) -> Msg {
+        let (before, after) = self.neighbours(target);
+        Msg::OpenPaper {
+            before,
+            target,
+            after,
+        }
+    }
+
+    /// The visible papers before and after `target`, none if it is not visible.
+    fn neighbours(&self, target: u64) -> (Option<u64>, Option<u64>) {
+        let papers = self.visible_papers();
+        let pos = papers.iter().position(|e| e.pid == target);
+        (
+            pos.and_then(|pos| pos.checked_sub(1))
+                .and_then(|pos| papers.get(pos))
+                .map(|e| e.pid),
+            pos.and_then(|pos| papers.get(pos + 1)).map(|e| e.pid),
+        )
+    }
+
+    /// Opens the paper after the selected one, or before it if not `down`, going
+    /// round to the other end of the list with [`Config::wrap_navigation`].
+    fn step_selection(&mut self, down: bool) -> Command<Msg> {
+        let Some(selected) = self.board().selected_paper else {
+            return Command::none();
+        };
+        let target = if down {
+            self.board().related_papers.1
+        } else {
+            self.board().related_papers.0
+        }
+        .or_else(|| {
+            let papers = self.visible_papers();
+            if down { papers.first() } else { papers.last() }
+                .map(|e| e.pid)
+                .filter(|pid| self.board().static_ins.config.wrap_navigation && *pid != selected)
+        });
+        match target {
+            Some(pid) => self.update(self.open_paper(pid)),
+            None => Command::none(),
+        }
+    }
+
+    /// Moves a selection whose paper is gone on to the next visible paper in `previous`,
+    /// the display order before papers were dropped, or the one before it if none follows.
+    /// A selection still loaded only gets its neighbours updated.
+    fn reconcile_selection(&mut self, previous: Vec<u64>) -> Command<Msg> {
+        let Some(selected) = self.board().selected_paper else {
+            return Command::none();
+        };
+        if self.board().papers.contains_key(&selected) {
+            self.board_mut().related_papers = self.neighbours(selected);
+            return Command::none();
+        }
+        let visible: HashSet<u64> = self.visible_papers().iter().map(|e| e.pid).collect();
+        let pos = previous
+            .iter()
+            .position(|pid| *pid == selected)
+            .unwrap_or(previous.len());
+        let next = previous[pos..]
+            .iter()
+            .chain(previous[..pos].iter().rev())
+            .find(|pid| visible.contains(pid))
+            .copied();
+        match next {
+            Some(pid) => self.update(self.open_paper(pid)),
+            None => {
+                self.board_mut().selected_paper = None;
+                self.board_mut().related_papers = (None, None);
+                Command::none()
+            }
+        }
+    }
+
+    /// Columns of a list row's text that fit the list, estimated from the font size.
+    fn list_row_columns(&self) -> usize {
+        let width = self.bar_width() - LIST_ROW_DECORATION_WIDTH;
+        ((width / (self.board().static_ins.config.list_font_size * AVERAGE_CHAR_WIDTH)) as usize)
+            .max(10)
+    }
+
+    /// Width available to the top bar of the paper list.
+    fn bar_width(&self) -> f32 {
+        match self.split_axis {
+            iced_aw::split::Axis::Vertical => self
+                .split_0_pos
+                .map_or(self.window_size.0 as f32 / self.text_scale / 2.0, f32::from),
+            iced_aw::split::Axis::Horizontal => self.window_size.0 as f32 / self.text_scale,
+        }
+    }
+
+    fn blocklist_view(&self) -> iced::Element<'_, Msg, iced::Renderer<iced::Theme>> {
+        let mut col = Column::new()
+            .spacing(5)
+            .push(Text::new("BLOCKED PAPERS").style(Color::new(0.5, 0.5, 0.5, 1.0)));
+        let blocked = self
+            .state
+            .blocked
+            .get(self.board().static_ins.config.board_name());
+        if blocked.is_none() {
+            col = col.push(Text::new("No blocked papers."));
+        }
+        for &pid in blocked.into_iter().flatten() {
+            col = col.push(
+                Row::new()
+                    .push(
+                        Text::new(format!("#{pid}"))
+                            .width(Length::Fill)
+                            .vertical_alignment(iced::alignment::Vertical::Center),
+                    )
+                    .push(
+                        button(Text::new("Unblock"))
+                            .style(theme::Button::Text)
+                            .on_press(Msg::Unblock(pid)),
+                    ),
+            );
+        }
+        container(Scrollable::new(col))
+            .width(300)
+            .max_height(400)
+            .padding(15)
+            .style(theme::Container::Box)
+            .into()
+    }
+
+    fn import_view(&self) -> iced::Element<'_, Msg, iced::Renderer<iced::Theme>> {
+        let mut col = Column::new()
+            .spacing(5)
+            .push(Text::new("IMPORT DECISIONS").style(Color::new(0.5, 0.5, 0.5, 1.0)))
+            .push(
+                Row::new()
+                    .spacing(5)
+                    .push(
+                        text_input("decisions.csv or decisions.json", &self.import.path)
+                            .on_input(Msg::ImportPathChanged)
+                            .on_submit(Msg::Import)
+                            .padding(5),
+                    )
+                    .push(
+                        button(Text::new("Browse…"))
+                            .style(theme::Button::Secondary)
+                            .on_press_maybe(
+                                (!self.import.running()).then_some(Msg::PickImportFile),
+                            ),
+                    )
+                    .push(
+                        button(Text::new("Import"))
+                            .on_press_maybe((!self.import.running()).then_some(Msg::Import)),
+                    ),
+            );
+
+        if self.import.total > 0 {
+            let failed: Vec<String> = self
+                .import
+                .results
+                .iter()
+                .filter(|(_, p)| !p)
+                .map(|(pid, _)| format!("#{pid}"))
+                .collect();
+            col = col.push(Text::new(format!(
+                "Applied {}/{}, {} failed",
+                self.import.results.len(),
+                self.import.total,
+                failed.len()
+            )));
+            if !failed.is_empty() {
+                col = col.push(Text::new(format!("Failed: {}", failed.join(", "))));
+            }
+        }
+        for skipped in &self.import.skipped {
+            col = col.push(Text::new(skipped).style(Color::new(0.5, 0.5, 0.5, 1.0)));
+        }
+
+        container(Scrollable::new(col))
+            .width(400)
+            .max_height(400)
+            .padding(15)
+            .style(theme::Container::Box)
+            .into()
+    }
+
+    fn export_view(&self) -> iced::Element<'_, Msg, iced::Renderer<iced::Theme>> {
+        let mut col = Column::new()
+            .spacing(5)
+            .push(Text::new("EXPORT PAPERS").style(Color::new(0.5, 0.5, 0.5, 1.0)))
+            .push(
+                text_input("papers.csv or papers.json", &self.export.path)
+                    .on_input(Msg::ExportPathChanged)
+                    .on_submit(Msg::Export)
+                    .padding(5),
+            )
+            .push(
+                Row::new()
+                    .spacing(5)
+                    .push(
+                        text_input("From YYYY-MM-DD", &self.export.from)
+                            .on_input(Msg::ExportFromChanged)
+                            .on_submit(Msg::Export)
+                            .padding(5),
+                    )
+                    .push(
+                        text_input("To YYYY-MM-DD", &self.export.to)
+                            .on_input(Msg::ExportToChanged)
+                            .on_submit(Msg::Export)
+                            .padding(5),
+                    )
+                    .push(button(Text::new("Export")).on_press(Msg::Export)),
+            );
+
+        match &self.export.result {
+            Some(Ok(count)) => col = col.push(Text::new(format!("Exported {count} papers"))),
+            Some(Err(err)) => {
+                col = col.push(Text::new(err).style(self.theme().palette().danger));
+            }
+            None => (),
+        }
+
+        container(col)
+            .width(400)
+            .padding(15)
+            .style(theme::Container::Box)
+            .into()
+    }
+
+    fn history_view(&self) -> iced::Element<'_, Msg, iced::Renderer<iced::Theme>> {
+        let mut col = Column::new()
+            .spacing(5)
+            .push(Text::new("HISTORY").style(Color::new(0.5, 0.5, 0.5, 1.0)));
+        for paper in &self.board().history.papers {
+            col = col.push(
+                Row::new()
+                    .spacing(10)
+                    .push(Text::new(format!("{}: {}", paper.name, paper.info)).width(Length::Fill))
+                    .push(
+                        Text::new(
+                            self.board()
+                                .static_ins
+                                .config
+                                .time_format
+                                .format(&paper.time),
+                        )
+                        .style(Color::new(0.5, 0.5, 0.5, 1.0)),
+                    ),
+            );
+        }
+        if self.board().history.loading {
+            col = col.push(Text::new("Loading…").style(Color::new(0.5, 0.5, 0.5, 1.0)));
+        } else if self.board().history.exhausted && self.board().history.papers.is_empty() {
+            col = col.push(Text::new("No processed papers."));
+        } else if !self.board().history.exhausted {
+            // A page too short to scroll never reaches the end by scrolling.
+            col = col.push(
+                button(Text::new("Load more"))
+                    .style(theme::Button::Text)
+                    .on_press(Msg::LoadHistory),
+            );
+        }
+
+        container(
+            Scrollable::new(col)
+                .height(Length::Fill)
+                .on_scroll(Msg::HistoryScrolled),
+        )
+        .width(500)
+        .height(400)
+        .padding(15)
+        .style(theme::Container::Box)
+        .into()
+    }
+
+    fn errors_view(&self) -> iced::Element<'_, Msg, iced::Renderer<iced::Theme>> {
+        let mut col = Column::new()
+            .spacing(5)
+            .push(Text::new("ERROR HISTORY").style(Color::new(0.5, 0.5, 0.5, 1.0)));
+        for (time, err) in self.board().error_history.iter().rev() {
+            col = col.push(
+                Row::new()
+                    .spacing(10)
+                    .push(Text::new(err).width(Length::Fill))
+                    .push(
+                        Text::new(self.board().static_ins.config.time_format.format(time))
+                            .style(Color::new(0.5, 0.5, 0.5, 1.0)),
+                    ),
+            );
+        }
+        if self.board().error_history.is_empty() {
+            col = col.push(Text::new("No refresh failed."));
+        }
+
+        container(Scrollable::new(col).height(Length::Fill))
+            .width(500)
+            .height(400)
+            .padding(15)
+            .style(theme::Container::Box)
+            .into()
+    }
+
+    fn help_view(&self) -> iced::Element<'_, Msg, iced::Renderer<iced::Theme>> {
+        let section = |title| Text::new(title).style(Color::new(0.5, 0.5, 0.5, 1.0));
+        let entry = |key, description| {
+            Row::new()
+                .spacing(10)
+                .push(Text::new(key).width(170).font(Font::MONOSPACE))
+                .push(Text::new(description).width(Length::Fill))
+        };
+        let mut col = Column::new().spacing(5).push(section("KEYBOARD SHORTCUTS"));
+        for (key, description) in SHORTCUTS {
+            col = col.push(entry(key, description));
+        }
+        col = col
+            .push(vertical_space(10))
+            .push(section("SEARCH PREFIXES"));
+        for (prefix, description) in search::PREFIXES {
+            col = col.push(entry(prefix, description));
+        }
+        col = col.push(vertical_space(10)).push(section("HOOK VARIABLES"));
+        for (name, description) in hooks::VARIABLES {
+            col = col.push(entry(name, description));
+        }
+
+        container(Scrollable::new(col).height(Length::Fill))
+            .width(500)
+            .height(500)
+            .padding(15)
+            .style(theme::Container::Box)
+            .into()
+    }
+
+    /// Decisions made this session, under the paper list.
+    fn session_stats_view(&self) -> iced::Element<'_, Msg, iced::Renderer<iced::Theme>> {
+        let count = |glyph, count, accepted| {
+            Row::new()
+                .spacing(3)
+                .push(
+                    Text::new(glyph)
+                        .size(13.5)
+                        .font(self.nerd_font)
+                        .style(self.decision_color(accepted)),
+                )
+                .push(Text::new(format!("{count}")).size(13.5))
+        };
+        container(
+            Row::new()
+                .height(30)
+                .spacing(10)
+                .align_items(iced::Alignment::Center)
+                .push(
+                    Text::new("   THIS SESSION")
+                        .size(13.5)
+                        .style(Color::new(0.5, 0.5, 0.5, 1.0)),
+                )
+                .push(count("", self.accepted_this_session, true))
+                .push(count("", self.rejected_this_session, false))
+                .push(horizontal_space(Length::Fill))
+                .push(
+                    button(Text::new("reset").size(13.5))
+                        .style(theme::Button::Text)
+                        .on_press(Msg::ResetSessionStats),
+                ),
+        )
+        .width(Length::Fill)
+        .into()
+    }
+
+    /// Placeholder of the paper list, telling an empty board from one filtered to nothing.
+    fn empty_list_view(&self) -> iced::Element<'_, Msg, iced::Renderer<iced::Theme>> {
+        let (glyph, message) = if self.board().papers.is_empty() {
+            (
+                "",
+                "No papers yet, they will appear here after the next refresh",
+            )
+        } else {
+            ("", "No papers match the search or filters")
+        };
+        container(
+            Column::new()
+                .spacing(10)
+                .align_items(iced::Alignment::Center)
+                .push(
+                    Text::new(glyph)
+                        .size(30)
+                        .style(Color::new(0.5, 0.5, 0.5, 1.0))
+                        .font(self.nerd_font),
+                )
+                .push(
+                    Text::new(message)
+                        .horizontal_alignment(iced::alignment::Horizontal::Center)
+                        .style(Color::new(0.5, 0.5, 0.5, 1.0)),
+                ),
+        )
+        .width(Length::Fill)
+        .height(Length::Fill)
+        .padding(15)
+        .center_x()
+        .center_y()
+        .into()
+    }
+
+    fn confirm_select_view(&self) -> iced::Element<'_, Msg, iced::Renderer<iced::Theme>> {
+        container(
+            Column::new()
+                .spacing(15)
+                .push(Text::new(format!(
+                    "Select all {} matching papers?",
+                    self.visible_papers().len()
+                )))
+                .push(
+                    Row::new()
+                        .spacing(10)
+                        .push(
+                            button(Text::new("Select"))
+                                .style(theme::Button::Primary)
+                                .on_press(Msg::SelectMatching { confirmed: true }),
+                        )
+                        .push(
+                            button(Text::new("Cancel"))
+                                .style(theme::Button::Secondary)
+                                .on_press(Msg::CloseOverlay),
+                        ),
+                ),
+        )
+        .padding(15)
+        .style(theme::Container::Box)
+        .into()
+    }
+
+    fn confirm_accept_all_view(&self) -> iced::Element<'_, Msg, iced::Renderer<iced::Theme>> {
+        let count = self
+            .visible_papers()
+            .iter()
+            .filter(|e| e.processed.is_none())
+            .count();
+        container(
+            Column::new()
+                .spacing(15)
+                .push(Text::new(match count {
+                    1 => "Accept the pending paper shown?".to_owned(),
+                    count => format!("Accept all {count} pending papers shown?"),
+                }))
+                .push(
+                    Row::new()
+                        .spacing(10)
+                        .push(
+                            button(Text::new("Accept"))
+                                .style(theme::Button::Positive)
+                                .on_press(Msg::AcceptAllVisible { confirmed: true }),
+                        )
+                        .push(
+                            button(Text::new("Cancel"))
+                                .style(theme::Button::Secondary)
+                                .on_press(Msg::CloseOverlay),
+                        ),
+                ),
+        )
+        .padding(15)
+        .style(theme::Container::Box)
+        .into()
+    }
+
+    fn reject_reason_view(&self) -> iced::Element<'_, Msg, iced::Renderer<iced::Theme>> {
+        let mut col = Column::new().spacing(5).push(
+            Text::new(match self.board().reject_pending.len() {
+                1 => "REJECT WITH REASON".to_owned(),
+                count => format!("REJECT {count} PAPERS WITH REASON"),
+            })
+            .style(Color::new(0.5, 0.5, 0.5, 1.0)),
+        );
+        for (i, reason) in self
+            .board()
+            .static_ins
+            .config
+            .reject_reasons
+            .iter()
+            .enumerate()
+        {
+            let label = match i {
+                0..=8 => format!("{}  {reason}", i + 1),
+                _ => format!("   {reason}"),
+            };
+            col = col.push(
+                button(Text::new(label))
+                    .width(Length::Fill)
+                    .style(theme::Button::Text)
+                    .on_press(Msg::RejectWithReason(i)),
+            );
+        }
+
+        let reject = button(Text::new("Reject"))
+            .style(theme::Button::Destructive)
+            .on_press_maybe(self.reject_note_accepted().then_some(Msg::RejectWithNote));
+        // Says why the button is disabled.
+        let reject: iced::Element<'_, Msg, iced::Renderer<iced::Theme>> =
+            if self.reject_note_accepted() {
+                reject.into()
+            } else {
+                tooltip(reject, "A reason is required", tooltip::Position::Top)
+                    .style(theme::Container::Box)
+                    .into()
+            };
+        col = col.push(
+            Row::new()
+                .spacing(5)
+                .push(
+                    text_input("Reason", &self.reject_note)
+                        .id(text_input::Id::new(REJECT_NOTE_INPUT_ID))
+                        .on_input(Msg::RejectNoteChanged)
+                        .on_submit(Msg::RejectWithNote)
+                        .padding(5),
+                )
+                .push(reject),
+        );
+
+        container(Scrollable::new(col))
+            .width(400)
+            .max_height(400)
+            .padding(15)
+            .style(theme::Container::Box)
+            .into()
+    }
+
+    /// The info of a paper, rendered as markdown if [`Config::render_markdown`] is set.
+    fn info_view<'a>(&self, info: &'a str) -> iced::Element<'a, Msg, iced::Renderer<iced::Theme>> {
+        let blocks = if self.board().static_ins.config.render_markdown {
+            markdown::parse(info)
+        } else {
+            vec![]
+        };
+        if blocks.is_empty() {
+            // Wraps within the pane, growing the colored background with it.
+            let text = container(Text::new(info).size(18.5).width(Length::Fill))
+                .width(Length::Fill)
+                .padding([0, 10]);
+            let links = linkify::links(info);
+            if links.is_empty() {
+                return text.into();
+            }
+            let mut buttons = Column::new().spacing(5).padding([0, 10]);
+            for url in links {
+                buttons = buttons.push(self.link_button(url.to_owned(), url.to_owned()));
+            }
+            return Column::new().spacing(5).push(text).push(buttons).into();
+        }
+
+        let si: &'static StaticIns = self.board().static_ins;
+        let mut col = Column::new().spacing(5).padding([5, 10]);
+        for block in blocks {
+            let text = Text::new(block.text);
+            let element: iced::Element<'a, Msg, iced::Renderer<iced::Theme>> = match block.kind {
+                markdown::Kind::Heading(level) => text
+                    .size(match level {
+                        HeadingLevel::H1 => 26.0,
+                        HeadingLevel::H2 => 22.0,
+                        _ => 20.0,
+                    })
+                    .font(Font {
+                        weight: iced::font::Weight::Bold,
+                        ..Font::with_name(&si.config.font)
+                    })
+                    .into(),
+                markdown::Kind::Paragraph => text.size(18.5).into(),
+                markdown::Kind::Item(depth) => container(text.size(18.5))
+                    .padding([0, 0, 0, 15 * depth as u16])
+                    .into(),
+                markdown::Kind::Code => text.size(16.0).font(Font::MONOSPACE).into(),
+            };
+            col = col.push(element);
+            for (label, url) in block.links {
+                col = col.push(self.link_button(label, url));
+            }
+        }
+        col.into()
+    }
+
+    /// A glyph button copying `text` to the clipboard, placed after a detail.
+    fn copy_button<'a>(&self, text: String) -> iced::Element<'a, Msg, iced::Renderer<iced::Theme>> {
+        button(
+            Text::new("")
+                .size(13.5)
+                .style(Color::new(0.5, 0.5, 0.5, 1.))
+                .font(self.nerd_font),
+        )
+        .padding([0, 8])
+        .style(theme::Button::Text)
+        .on_press(Msg::Copy(text))
+        .into()
+    }
+
+    /// A glyph button copying all details of `paper`, sized like the action buttons.
+    fn copy_details_button<'a>(
+        &self,
+        paper: u64,
+    ) -> iced::Element<'a, Msg, iced::Renderer<iced::Theme>> {
+        button(
+            Text::new("")
+                .size(16.5)
+                .height(35)
+                .width(35)
+                .horizontal_alignment(iced::alignment::Horizontal::Center)
+                .vertical_alignment(iced::alignment::Vertical::Center)
+                .style(Color::new(0.5, 0.5, 0.5, 1.))
+                .font(self.nerd_font),
+        )
+        .style(theme::Button::Text)
+        .on_press(Msg::CopyDetails(paper))
+        .into()
+    }
+
+    /// Where an attachment is opened from, relative ones resolved against the host.
+    fn attachment_url(&self, attachment: &str) -> String {
+        reqwest::Url::parse(attachment)
+            .or_else(|_| {
+                reqwest::Url::parse(&self.board().static_ins.config.host_url)
+                    .and_then(|host| host.join(attachment))
+            })
+            .map_or_else(|_| attachment.to_owned(), String::from)
+    }
+
+    /// A link in paper info, opened in the browser when pressed.
+    fn link_button<'a>(
+        &self,
+        label: String,
+        url: String,
+    ) -> iced::widget::Button<'a, Msg, iced::Renderer<iced::Theme>> {
+        button(Text::new(label).style(self.theme().palette().primary))
+            .padding(0)
+            .style(theme::Button::Text)
+            .on_press(Msg::OpenLink(url))
+    }
+
+    /// A nerd-font glyph button as used in the top bar.
+    fn icon_button(
+        &self,
+        glyph: &'static str,
+        msg: Msg,
+    ) -> iced::Element<'_, Msg, iced::Renderer<iced::Theme>> {
+        button(
+            Text::new(glyph)
+                .width(ICON_BUTTON_WIDTH)
+                .height(30)
+                .size(13.5)
+                .horizontal_alignment(iced::alignment::Horizontal::Center)
+                .style(Color::new(0.5, 0.5, 0.5, 1.0))
+                .font(self.nerd_font),
+        )
+        .style(theme::Button::Text)
+        .on_press(msg)
+        .into()
+    }
+
+    /// Posts `decision` on `paper`, answered by `Accepted` or `Rejected`.
+    fn decide(&mut self, paper: u64, decision: Decision, reason: Option<String>) -> Command<Msg> {
+        if decision == Decision::Accept && self.board().static_ins.config.optimistic_accept {
+            if let Some(value) = self.board_mut().papers.get_mut(&paper) {
+                value.processed = Some(true)
+            }
+        }
+        // Failures of a batch are reported together once all of it returned.
+        let in_batch = self
+            .board()
+            .accept_batch
+            .as_ref()
+            .is_some_and(|batch| batch.pending.contains(&paper));
+        Command::perform(
+            guarded({
+                let si = self.board().static_ins;
+                async move { post_decision(si, paper, decision, reason.as_deref(), None).await }
+            }),
+            move |result| match result {
+                Ok(Ok(())) => Msg::decided(paper, decision, true),
+                Ok(Err(_)) if in_batch => Msg::decided(paper, decision, false),
+                Ok(Err(err)) => Msg::Multi(vec![
+                    Msg::decided(paper, decision, false),
+                    Msg::BackendError(err),
+                ]),
+                Err(panic) => Msg::Multi(vec![
+                    Msg::decided(paper, decision, false),
+                    Msg::TaskPanicked(panic),
+                ]),
+            },
+        )
+    }
+
+    fn decided(&mut self, paper: u64, decision: Decision, p: bool) -> Command<Msg> {
+        let mut flash_end = Command::none();
+        let mut hook = Command::none();
+        let si = self.board().static_ins;
+        let board = &mut self.boards[self.active_board];
+        if let Some(value) = board.papers.get_mut(&paper) {
+            if p {
+                board.re_reviewing.remove(&paper);
+                value.processed = Some(decision == Decision::Accept);
+                value.confirmed = true;
+                value.processed_at = Some(chrono::Utc::now());
+                Metrics::bump(match decision {
+                    Decision::Accept => &si.metrics.accepted,
+                    Decision::Reject => &si.metrics.rejected,
+                });
+                *match decision {
+                    Decision::Accept => &mut self.accepted_this_session,
+                    Decision::Reject => &mut self.rejected_this_session,
+                } += 1;
+                push_undo(
+                    &mut board.undo_stack,
+                    Paper {
+                        processed: None,
+                        confirmed: false,
+                        processed_at: None,
+                        ..value.clone()
+                    },
+                );
+                let event = match decision {
+                    Decision::Accept => hooks::Event::Accept,
+                    Decision::Reject => hooks::Event::Reject,
+                };
+                if si.config.hooks.has(event) {
+                    hook = run_hook(si, event, value.clone());
+                }
+                let flash = si.config.decision_flash_ms;
+                if flash > 0 {
+                    board.flashes.insert(paper, Instant::now());
+                    flash_end = Command::perform(
+                        tokio::time::sleep(Duration::from_millis(flash)),
+                        move |_| Msg::FlashEnded(paper),
+                    );
+                }
+            } else {
+                // The paper stays pending, also reverting an optimistic accept.
+                value.processed = None;
+                let in_batch = board
+                    .accept_batch
+                    .as_ref()
+                    .is_some_and(|batch| batch.pending.contains(&paper));
+                // Papers of a batch are reported with it by `batch_done`.
+                if !in_batch {
+                    board.last_error = Some(format!(
+                        "Failed to {} paper from {}",
+                        match decision {
+                            Decision::Accept => "accept",
+                            Decision::Reject => "reject",
+                        },
+                        value.name
+                    ));
+                }
+            }
+        }
+        self.sync_backlog();
+        let follow_up = match self.board_mut().accept_batch.as_mut() {
+            Some(batch) if batch.pending.contains(&paper) => {
+                batch.pending.remove(&paper);
+                if !p {
+                    batch.failed.push(paper);
+                }
+                // One refresh and one report for the whole batch.
+                if batch.pending.is_empty() {
+                    self.batch_done()
+                } else {
+                    Command::none()
+                }
+            }
+            _ => self.after_action(),
+        };
+        Command::batch([flash_end, hook, follow_up])
+    }
+
+    /// Reports the failures of the finished [`Board::accept_batch`].
+    fn batch_done(&mut self) -> Command<Msg> {
+        if let Some(batch) = self
+            .board_mut()
+            .accept_batch
+            .take()
+            .filter(|batch| !batch.failed.is_empty())
+        {
+            self.board_mut().last_error = Some(format!(
+                "Failed to accept {} of {} papers: {}",
+                batch.failed.len(),
+                batch.total,
+                batch
+                    .failed
+                    .iter()
+                    .map(u64::to_string)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ));
+        }
+        self.after_action()
+    }
+
+    /// Reconciles with the server after a mutating action if [`Config::auto_refresh_after_action`]
+    /// is set, bulk actions coalesce into a single follow-up refresh.
+    fn after_action(&self) -> Command<Msg> {
+        if self.board().static_ins.config.auto_refresh_after_action {
+            Command::perform(async {}, |_| Msg::Refresh)
+        } else {
+            Command::none()
+        }
+    }
 
-    split_0_pos: Option<u16>,
-    selected_paper: Option<u64>,
-    related_papers: (Option<u64>, Option<u64>),
-    nerd_font: Font,
-    dark_mode: bool,
-    split_axis: iced_aw::split::Axis,
-    display_bg: bool,
+    /// Copies the layout and the sort into the state and schedules saving it.
+    fn layout_changed(&mut self) -> Command<Msg> {
+        self.state.split_pos = self.split_0_pos;
+        self.state.split_horizontal = matches!(self.split_axis, iced_aw::split::Axis::Horizontal);
+        self.state.dark_mode = self.dark_mode;
+        self.state.window_size = Some(self.window_size);
+        self.state.text_scale = Some(self.text_scale);
+        self.state.sort = self.sort_key;
+        self.state.sort_ascending = self.sort_ascending;
+        self.save_state_later()
+    }
 
-    refresh_count: Arc<()>,
-}
+    /// Saves the state once it stopped changing for a moment.
+    fn save_state_later(&mut self) -> Command<Msg> {
+        self.layout_changes += 1;
+        let change = self.layout_changes;
+        Command::perform(tokio::time::sleep(LAYOUT_SAVE_DEBOUNCE), move |_| {
+            Msg::SaveLayout(change)
+        })
+    }
 
-impl Application for App {
-    type Executor = iced_futures::backend::native::tokio::Executor;
+    /// Opacity of the decision highlight of `paper`, if it was just decided.
+    fn flash_alpha(&self, paper: &Paper) -> Option<f32> {
+        const PEAK: f32 = 0.4;
+        let at = self.board().flashes.get(&paper.pid)?;
+        paper.processed?;
+        if self.board().static_ins.config.reduced_motion {
+            return Some(PEAK);
+        }
+        let progress = at.elapsed().as_secs_f32()
+            / Duration::from_millis(self.board().static_ins.config.decision_flash_ms).as_secs_f32();
+        (progress < 1.0).then_some(PEAK * (1.0 - progress))
+    }
 
-    type Message = Msg;
+    /// Color marking accepted or rejected papers, the configured one or the theme's.
+    fn decision_color(&self, accepted: bool) -> Color {
+        let config = &self.board().static_ins.config;
+        let palette = self.theme().palette();
+        if accepted {
+            config.accept_color.map_or(palette.success, style::from_hex)
+        } else {
+            config.reject_color.map_or(palette.danger, style::from_hex)
+        }
+    }
 
-    type Theme = iced::Theme;
+    /// Whether the typed reject reason may be submitted, [`Config::require_reject_reason`]
+    /// refusing a blank one.
+    fn reject_note_accepted(&self) -> bool {
+        !self.board().static_ins.config.require_reject_reason || !self.reject_note.trim().is_empty()
+    }
 
-    type Flags = Config;
+    /// Whether papers can be rejected, which needs a reject endpoint.
+    fn can_reject(&self) -> bool {
+        self.board().static_ins.host.reject_paper.is_some()
+            || self.board().static_ins.mock.is_some()
+    }
 
-    fn new(flags: Self::Flags) -> (Self, iced::Command<Self::Message>) {
-        (
-            Self {
-                papers: HashMap::new(),
-                static_ins: Box::leak(Box::new(StaticIns {
-                    host: BuiltHost {
-                        paper_need_process: format!(
-                            "{}{}/{}",
-                            flags.host_url, flags.global_mapping, flags.paper_need_process_mapping
-                        ),
-                        process_paper: format!(
-                            "{}{}/{}",
-                            flags.host_url, flags.global_mapping, flags.process_paper_mapping
-                        ),
-                    },
-                    client: reqwest::Client::new(),
-                })),
-                split_0_pos: Some(250),
-                selected_paper: None,
-                related_papers: (None, None),
-                nerd_font: Font::MONOSPACE,
-                dark_mode: false,
-                split_axis: iced_aw::split::Axis::Vertical,
-                display_bg: true,
-                refresh_count: Arc::new(()),
+    /// State for bug reports, leaving out paper contents and secrets.
+    fn debug_dump(&self) -> serde_json::Value {
+        let si = self.board().static_ins;
+        let host = &si.host;
+        serde_json::json!({
+            "papers": self.board().papers.len(),
+            "pending": self.board().papers.values().filter(|e| e.processed.is_none()).count(),
+            "visible": self.visible_papers().len(),
+            "selected_paper": self.board().selected_paper,
+            "related_papers": self.board().related_papers,
+            "multi_selected": self.board().multi_selected,
+            "re_reviewing": self.board().re_reviewing,
+            "search_query": self.search_query,
+            "stale_only": self.stale_only,
+            "filter": format!("{:?}", self.filter),
+            "overlay": self.overlay.map(|overlay| format!("{overlay:?}")),
+            "dark_mode": self.dark_mode,
+            "split_axis": format!("{:?}", self.split_axis),
+            "window_size": self.window_size,
+            "text_scale": self.text_scale,
+            "refreshing": self.refreshing(),
+            "refresh_requested": self.board().refresh_requested,
+            "retrying": self.board().retry_at.is_some(),
+            "consecutive_failures": self.board().retry_backoff.failures(),
+            "last_refresh": self.board().last_refresh.map(|time| time.to_rfc3339()),
+            "last_error": self.board().last_error,
+            "undo_depth": self.board().undo_stack.len(),
+            "blocked": self.state.blocked,
+            "mock": si.mock.is_some(),
+            "api_token": si.config.api_token.as_ref().map(|_| "<redacted>"),
+            "urls": {
+                "paper_need_process": redact_url(&host.paper_need_process),
+                "process_paper": redact_url(&host.process_paper),
+                "reject_paper": host.reject_paper.as_deref().map(redact_url),
+                "view_receipt": host.view_receipt.as_deref().map(redact_url),
+                "unprocess": host.unprocess.as_deref().map(redact_url),
+                "history": host.history.as_deref().map(redact_url),
             },
-            Command::batch([
-                Command::perform(async {}, |_| Msg::RefreshLoop(Duration::ZERO)),
-                iced::font::load(
-                    include_bytes!("../fonts/SymbolsNerdFontMono-Regular.ttf").as_slice(),
-                )
-                .map(Msg::FontLoaded),
-            ]),
-        )
+        })
     }
 
-    #[inline]
-    fn title(&self) -> String {
-        format!(
-            "SubBoard{}",
-            if let Some(value) = self.selected_paper.and_then(|v| self.papers.get(&v)) {
-                format!(" - Paper from {}", value.name)
-            } else {
-                Default::default()
-            }
-        )
+    /// Moves the range cursor one step and reselects every paper
+    /// between it and the anchor.
+    fn extend_range(&mut self, down: bool) {
+        let Some(anchor) = self.board().selected_paper else {
+            return;
+        };
+        let pids: Vec<u64> = self.visible_papers().iter().map(|e| e.pid).collect();
+        let cursor = self.board().range_cursor.unwrap_or(anchor);
+        let (Some(a), Some(c)) = (
+            pids.iter().position(|&e| e == anchor),
+            pids.iter().position(|&e| e == cursor),
+        ) else {
+            return;
+        };
+        let c = if down {
+            (c + 1).min(pids.len() - 1)
+        } else {
+            c.saturating_sub(1)
+        };
+
+        self.board_mut().range_cursor = Some(pids[c]);
+        self.board_mut().multi_selected = pids[a.min(c)..=a.max(c)].iter().copied().collect();
     }
 
-    fn update(&mut self, message: Self::Message) -> iced::Command<Self::Message> {
+    /// Handles `message` for the active board, see [`Msg::OnBoard`].
+    fn update_board(&mut self, message: Msg) -> Command<Msg> {
+        if self.closing {
+            // Late results, e.g. the font load or a refresh tick, must not spawn new work.
+            return Command::none();
+        }
+
         match message {
-            Msg::Split0Resized(s) => self.split_0_pos = Some(s),
+            Msg::Split0Resized(s) => {
+                self.split_0_pos = Some(s);
+                return self.layout_changed();
+            }
+            // Only the last of a burst of changes is written.
+            Msg::SaveLayout(change) if change == self.layout_changes => {
+                self.state
+                    .save(&self.board().static_ins.config.state_path());
+                self.saved_layout = change;
+            }
+            Msg::Refresh if self.refreshing() => {
+                // Coalesce into a single follow-up refresh.
+                self.board_mut().refresh_requested = true;
+            }
             Msg::Refresh => {
-                let arc = self.refresh_count.clone();
+                let arc = self.board().refresh_count.clone();
+                let si = self.board().static_ins;
+                self.board_mut().refresh_started = Some(chrono::Utc::now());
+                let since = self
+                    .board()
+                    .last_refresh
+                    .filter(|_| si.config.delta_refresh)
+                    .map(|time| time.to_rfc3339());
                 return Command::perform(
-                    async {
+                    guarded(async move {
                         let _count: Arc<_> = arc;
                         let span = tracing::span!(tracing::Level::INFO, "refresh papers");
                         tracing::event!(tracing::Level::INFO, "refreshing papers");
                         let _span = span.enter();
 
-                        Msg::RefreshDone(
-                            self.static_ins
-                                .client
-                                .get(&self.static_ins.host.paper_need_process)
-                                .send()
-                                .and_then(|res| res.json())
-                                .unwrap_or_else(|err| {
-                                    tracing::event!(tracing::Level::ERROR, "{err}");
-                                    vec![]
-                                })
-                                .await,
-                        )
+                        if let Some(mock) = &si.mock {
+                            return Msg::RefreshDone(Ok(mock.pending().await));
+                        }
+
+                        let mut request = si.client.get(&si.host.paper_need_process);
+                        if let Some(since) = since {
+                            request = request.query(&[("since", since)]);
+                        }
+                        let res = match request.send().await {
+                            Ok(res) => res,
+                            Err(err) => {
+                                tracing::event!(tracing::Level::ERROR, "{err}");
+                                return Msg::RefreshDone(Err(request_error(err)));
+                            }
+                        };
+                        let res = match backend_error(si, res).await {
+                            Ok(res) => res,
+                            Err(err) => {
+                                tracing::event!(tracing::Level::ERROR, "{err}");
+                                return Msg::RefreshDone(Err(err));
+                            }
+                        };
+                        Msg::RefreshDone(res.json().await.map_err(|err| {
+                            tracing::event!(tracing::Level::ERROR, "{err}");
+                            request_error(err)
+                        }))
+                    }),
+                    |result| {
+                        result.unwrap_or_else(|panic| {
+                            Msg::Multi(vec![
+                                Msg::RefreshDone(Err(panic.clone())),
+                                Msg::TaskPanicked(panic),
+                            ])
+                        })
                     },
-                    std::convert::identity,
                 );
             }
             Msg::RefreshLoop(duration) => {
-                let weak = Arc::downgrade(&self.refresh_count);
+                let weak = Arc::downgrade(&self.board().refresh_count);
+                let interval = self
+                    .board()
+                    .static_ins
+                    .config
+                    .refresh_interval(chrono::Local::now().time());
+                let backoff =
+                    Duration::from_secs(self.board().static_ins.config.refresh_backoff_secs);
                 return Command::perform(
                     async move {
+                        // Wall-clock time keeps running while the machine sleeps, unlike the timer.
+                        let start = SystemTime::now();
                         tokio::time::sleep(duration).await;
-                        weak.strong_count() == 1
+                        let resumed = start
+                            .elapsed()
+                            .is_ok_and(|slept| slept > duration + RESUME_GAP);
+                        if resumed {
+                            tracing::event!(tracing::Level::INFO, "resumed from sleep, refreshing");
+                        }
+                        // After a resume, queue a refresh even behind one still in flight.
+                        weak.strong_count() == 1 || resumed
                     },
-                    |p| {
+                    move |p| {
                         if p {
-                            Msg::Multi(vec![
-                                Msg::Refresh,
-                                Msg::RefreshLoop(Duration::from_secs(45)),
-                            ])
+                            Msg::Multi(vec![Msg::ScheduledRefresh, Msg::RefreshLoop(interval)])
                         } else {
-                            Msg::RefreshLoop(Duration::from_secs(30))
+                            Msg::RefreshLoop(backoff)
                         }
                     },
                 );
             }
-            Msg::RefreshDone(papers) => {
-                for paper in papers {
-                    self.papers.insert(paper.pid, paper);
+            Msg::RefreshDone(Ok(papers)) => {
+                Metrics::bump(&self.board().static_ins.metrics.refreshes);
+                // Unlike a delta refresh, a full one lists every pending paper.
+                let full = !self.board().static_ins.config.delta_refresh
+                    || self.board().last_refresh.is_none();
+                let listed: HashSet<u64> = papers.iter().map(|paper| paper.pid).collect();
+                self.board_mut().retry_at = None;
+                self.board_mut().retry_backoff.succeed();
+                self.board_mut().last_refresh = self.board_mut().refresh_started.take();
+                // Only clear the banner if nothing else was reported since.
+                let refresh_error = self.board_mut().refresh_error.take();
+                if self.board().last_error == refresh_error {
+                    self.board_mut().last_error = None;
+                }
+                let mut commands = vec![];
+                let new_paper_hook = self.board().loaded
+                    && self
+                        .board()
+                        .static_ins
+                        .config
+                        .hooks
+                        .has(hooks::Event::NewPaper);
+                for mut paper in papers {
+                    if self
+                        .state
+                        .is_blocked(self.board().static_ins.config.board_name(), paper.pid)
+                    {
+                        continue;
+                    }
+                    if self.board().static_ins.config.ignore_processed_from_backend
+                        && paper.processed.is_some()
+                    {
+                        tracing::event!(
+                            tracing::Level::WARN,
+                            "ignoring already processed paper {}",
+                            paper.pid
+                        );
+                        continue;
+                    }
+                    paper.confirmed = paper.processed.is_some();
+                    if self.board().re_reviewing.contains(&paper.pid) {
+                        paper.processed = None;
+                    }
+                    // The backend does not say when, keep what this session saw.
+                    if let Some(old) = self
+                        .board()
+                        .papers
+                        .get(&paper.pid)
+                        .filter(|old| old.processed == paper.processed)
+                    {
+                        paper.processed_at = old.processed_at;
+                    }
+                    if new_paper_hook
+                        && paper.processed.is_none()
+                        && !self.board().papers.contains_key(&paper.pid)
+                    {
+                        commands.push(run_hook(
+                            self.board().static_ins,
+                            hooks::Event::NewPaper,
+                            paper.clone(),
+                        ));
+                    }
+                    self.board_mut().papers.insert(paper.pid, paper);
+                }
+                if full {
+                    // Pending papers left out were decided elsewhere, also dropping them from
+                    // the cache. Papers put back into review here are kept.
+                    let board = self.board_mut();
+                    board.papers.retain(|pid, paper| {
+                        paper.processed.is_some()
+                            || listed.contains(pid)
+                            || board.re_reviewing.contains(pid)
+                    });
+                }
+                self.board_mut().loaded = true;
+                // Papers that left the board need not be remembered.
+                if let Some(seen) = self.state.seen.get_mut(
+                    self.boards[self.active_board]
+                        .static_ins
+                        .config
+                        .board_name(),
+                ) {
+                    seen.retain(|pid| self.boards[self.active_board].papers.contains_key(pid));
+                }
+                let previous = self.board().order.clone();
+                self.resort();
+                if self.board().static_ins.mock.is_none() {
+                    cache::save(
+                        &self.board().static_ins.config.cache_path(),
+                        self.board().papers.values(),
+                    );
+                }
+                commands.push(self.reconcile_selection(previous));
+                if std::mem::take(&mut self.board_mut().select_newest_pending) {
+                    commands.push(self.update(Msg::SelectNewest));
+                }
+                if std::mem::take(&mut self.board_mut().refresh_requested) {
+                    commands.push(self.update(Msg::Refresh));
+                }
+                return Command::batch(commands);
+            }
+            Msg::RefreshDone(Err(err)) => {
+                Metrics::bump(&self.board().static_ins.metrics.refresh_failures);
+                let err = format!("Failed to refresh: {err}");
+                let len = self.board().static_ins.config.error_history_len;
+                if len > 0 {
+                    if self.board().error_history.len() == len {
+                        self.board_mut().error_history.pop_front();
+                    }
+                    self.board_mut()
+                        .error_history
+                        .push_back((chrono::Utc::now(), err.clone()));
+                }
+                self.board_mut().last_error = Some(err.clone());
+                self.board_mut().refresh_error = Some(err);
+                // Fall back to a full refresh once the backend is reachable again.
+                self.board_mut().last_refresh = None;
+                self.board_mut().retry_at =
+                    Some(Instant::now() + self.board_mut().retry_backoff.fail());
+                if std::mem::take(&mut self.board_mut().refresh_requested) {
+                    return self.update(Msg::Refresh);
                 }
             }
+            Msg::SelectNewest => match self.visible_papers().first().map(|e| e.pid) {
+                Some(pid) => return self.update(self.open_paper(pid)),
+                // Nothing loaded yet, wait for the first refresh.
+                None => self.board_mut().select_newest_pending = true,
+            },
+            // While retrying, the backoff rather than the schedule decides when to refresh.
+            Msg::ScheduledRefresh if self.board().retry_at.is_some() => {}
+            Msg::ScheduledRefresh => return self.update(Msg::Refresh),
+            Msg::RetryNow => {
+                self.board_mut().retry_at = None;
+                return self.update(Msg::Refresh);
+            }
+            // A later decision on the same paper restarted its flash.
+            Msg::FlashEnded(paper)
+                if self.board().flashes.get(&paper).is_some_and(|at| {
+                    at.elapsed()
+                        >= Duration::from_millis(self.board().static_ins.config.decision_flash_ms)
+                }) =>
+            {
+                self.board_mut().flashes.remove(&paper);
+            }
             Msg::OpenPaper {
                 before,
                 target,
                 after,
             } => {
-                self.selected_paper = Some(target);
-                self.related_papers = (before, after);
-                self.display_bg = true
+                // Reselecting, e.g. by a double click, must not post another receipt.
+                let reopened = self.board().selected_paper == Some(target);
+                self.board_mut().selected_paper = Some(target);
+                self.board_mut().related_papers = (before, after);
+                self.display_bg = true;
+                self.board_mut().multi_selected.clear();
+                self.board_mut().range_cursor = None;
+
+                if reopened {
+                    return Command::none();
+                }
+                // A new paper is read from its start, wherever the last one was left.
+                let scroll_to_top = scrollable::snap_to(
+                    scrollable::Id::new(DETAIL_SCROLLABLE_ID),
+                    scrollable::RelativeOffset::START,
+                );
+                let seen = if self
+                    .state
+                    .seen
+                    .entry(self.board().static_ins.config.board_name().to_owned())
+                    .or_default()
+                    .insert(target)
+                {
+                    self.save_state_later()
+                } else {
+                    Command::none()
+                };
+                if self.board().static_ins.host.view_receipt.is_some() {
+                    return Command::batch([
+                        scroll_to_top,
+                        seen,
+                        Command::perform(tokio::time::sleep(VIEW_RECEIPT_DEBOUNCE), move |_| {
+                            Msg::ViewReceipt(target)
+                        }),
+                    ]);
+                }
+                return Command::batch([scroll_to_top, seen]);
+            }
+            Msg::ViewReceipt(paper) => {
+                // Only the paper the operator settled on gets a receipt.
+                if self.board().selected_paper != Some(paper) {
+                    return Command::none();
+                }
+                let si = self.board().static_ins;
+                return Command::perform(
+                    async move {
+                        let span = tracing::span!(tracing::Level::INFO, "view receipt {paper}");
+                        let _span = span.enter();
+
+                        if si.mock.is_some() {
+                            tracing::event!(tracing::Level::INFO, "mock receipt");
+                        } else if let Some(url) = si.host.view_receipt.as_deref() {
+                            // Nothing waits on receipts, but a refusing backend is worth a log line.
+                            if let Err(err) = si
+                                .client
+                                .post(url)
+                                .query(&[("pid", paper)])
+                                .send()
+                                .map_err(request_error)
+                                .and_then(|res| backend_error(si, res))
+                                .await
+                            {
+                                tracing::event!(tracing::Level::ERROR, "{err}");
+                            }
+                        }
+                    },
+                    |_| Msg::Noop,
+                );
+            }
+            Msg::Accept(paper) => return self.decide(paper, Decision::Accept, None),
+            Msg::Reject(paper) => return self.decide(paper, Decision::Reject, None),
+            Msg::PromptReject(pids)
+                if self.board().static_ins.config.reject_reasons.is_empty()
+                    && !self.board().static_ins.config.require_reject_reason =>
+            {
+                return self.update(Msg::Multi(pids.into_iter().map(Msg::Reject).collect()));
+            }
+            Msg::PromptReject(pids) => {
+                self.board_mut().reject_pending = pids;
+                self.reject_note.clear();
+                self.overlay = Some(Overlay::RejectReason);
+                // Nothing to pick, the reason has to be typed.
+                if self.board().static_ins.config.reject_reasons.is_empty() {
+                    return text_input::focus(text_input::Id::new(REJECT_NOTE_INPUT_ID));
+                }
+            }
+            Msg::RejectWithReason(reason) => {
+                let Some(reason) = self.board().static_ins.config.reject_reasons.get(reason) else {
+                    return Command::none();
+                };
+                self.overlay = None;
+                let pids = std::mem::take(&mut self.board_mut().reject_pending);
+                return Command::batch(
+                    pids.into_iter()
+                        .map(|pid| self.decide(pid, Decision::Reject, Some(reason.clone())))
+                        .collect::<Vec<_>>(),
+                );
             }
-            Msg::Accept(paper) => {
-                let si = self.static_ins;
+            Msg::RejectNoteChanged(note) => self.reject_note = note,
+            Msg::RejectWithNote if !self.reject_note_accepted() => {}
+            Msg::RejectWithNote => {
+                self.overlay = None;
+                let note = std::mem::take(&mut self.reject_note);
+                let reason = Some(note.trim().to_owned()).filter(|note| !note.is_empty());
+                let pids = std::mem::take(&mut self.board_mut().reject_pending);
+                return Command::batch(
+                    pids.into_iter()
+                        .map(|pid| self.decide(pid, Decision::Reject, reason.clone()))
+                        .collect::<Vec<_>>(),
+                );
+            }
+            // Loading again, e.g. a font the OS already provides, changes nothing.
+            Msg::FontLoaded(Ok(_)) if self.nerd_font == Font::with_name(NERD_FONT_FAMILY) => {}
+            Msg::FontLoaded(Ok(_)) if !declares_family(NERD_FONT, NERD_FONT_FAMILY) => {
+                tracing::event!(
+                    tracing::Level::WARN,
+                    "nerd font does not declare {NERD_FONT_FAMILY:?}, keeping the fallback"
+                )
+            }
+            Msg::FontLoaded(Ok(_)) => self.nerd_font = Font::with_name(NERD_FONT_FAMILY),
+            Msg::FontLoaded(Err(err)) => {
+                tracing::event!(tracing::Level::WARN, "failed to load nerd font: {err:?}")
+            }
+            Msg::Accepted(paper, p) => return self.decided(paper, Decision::Accept, p),
+            Msg::Rejected(paper, p) => return self.decided(paper, Decision::Reject, p),
+            Msg::Undo(paper) => {
+                // Decisions older than the stack, or not taken here, revert to pending.
+                let Some(snapshot) = self
+                    .board()
+                    .undo_stack
+                    .iter()
+                    .rposition(|e| e.pid == paper)
+                    .and_then(|pos| self.board_mut().undo_stack.remove(pos))
+                    .or_else(|| {
+                        self.board()
+                            .papers
+                            .get(&paper)
+                            .filter(|e| e.processed.is_some())
+                            .map(|e| Paper {
+                                processed: None,
+                                confirmed: false,
+                                processed_at: None,
+                                ..e.clone()
+                            })
+                    })
+                else {
+                    return Command::none();
+                };
+                let si = self.board().static_ins;
+                if si.mock.is_none() && si.host.unprocess.is_none() {
+                    push_undo(&mut self.board_mut().undo_stack, snapshot);
+                    self.board_mut().last_error =
+                        Some("Undo needs unprocess_mapping to be configured".into());
+                    return Command::none();
+                }
                 return Command::perform(
                     async move {
-                        let span = tracing::span!(tracing::Level::INFO, "accept paper {paper}");
+                        let span = tracing::span!(tracing::Level::INFO, "undo paper {paper}");
                         let _span = span.enter();
 
-                        if let Err(err) = si
-                            .client
-                            .post(&si.host.process_paper)
+                        if let Some(mock) = &si.mock {
+                            mock.unprocess(paper).await;
+                            return Ok(());
+                        }
+
+                        let url = si.host.unprocess.as_deref().unwrap_or_default();
+                        si.client
+                            .post(url)
                             .query(&[("pid", paper)])
                             .send()
+                            .map_err(request_error)
+                            .and_then(|res| backend_error(si, res))
                             .await
-                        {
-                            tracing::event!(tracing::Level::ERROR, "{err}");
-                            false
-                        } else {
-                            true
-                        }
+                            .map(|_| ())
+                            .inspect_err(|err| tracing::event!(tracing::Level::ERROR, "{err}"))
                     },
-                    move |p| Msg::Accepted(paper, p),
+                    move |result| Msg::Undone(snapshot, result),
                 );
             }
-            Msg::FontLoaded(Ok(_)) => self.nerd_font = Font::with_name("Symbols Nerd Font Mono"),
-            Msg::Accepted(paper, p) => {
-                if let Some(value) = self.papers.get_mut(&paper) {
-                    value.processed = Some(p)
+            Msg::Undone(snapshot, result) => {
+                match result {
+                    Ok(()) => {
+                        self.board_mut().papers.insert(snapshot.pid, snapshot);
+                        self.resort();
+                    }
+                    Err(err) => {
+                        self.board_mut().last_error = Some(format!(
+                            "Failed to undo paper from {}: {err}",
+                            snapshot.name
+                        ));
+                        push_undo(&mut self.board_mut().undo_stack, snapshot);
+                    }
                 }
-                return Command::perform(async {}, |_| Msg::Refresh);
+                return self.after_action();
             }
             Msg::ToggleDarkMode => {
                 self.dark_mode = !self.dark_mode;
+                return self.layout_changed();
             }
+            Msg::ToggleFocusMode => self.focus_mode = !self.focus_mode,
             Msg::SwitchSplitAxis => {
                 self.split_axis = match self.split_axis {
                     iced_aw::split::Axis::Horizontal => iced_aw::split::Axis::Vertical,
                     iced_aw::split::Axis::Vertical => iced_aw::split::Axis::Horizontal,
-                }
+                };
+                return self.layout_changed();
             }
             Msg::ToggleBg => self.display_bg = !self.display_bg,
-            Msg::CleanAccepted => self.papers.retain(|_, v| v.processed.is_none()),
+            Msg::Zoom(step) => {
+                self.text_scale =
+                    step.map_or(1.0, |step| ((self.text_scale + step) * 10.0).round() / 10.0);
+                self.text_scale = self
+                    .text_scale
+                    .clamp(*TEXT_SCALE_RANGE.start(), *TEXT_SCALE_RANGE.end());
+                return self.layout_changed();
+            }
+            Msg::SetSort(key) => {
+                if key == self.sort_key {
+                    self.sort_ascending = !self.sort_ascending;
+                } else {
+                    self.sort_key = key;
+                    self.sort_ascending = key.ascending_first();
+                }
+                self.resort();
+                return self.layout_changed();
+            }
+            Msg::ReReview(paper) => {
+                if let Some(value) = self.board_mut().papers.get_mut(&paper) {
+                    value.processed = None;
+                    value.confirmed = false;
+                    value.processed_at = None;
+                    self.board_mut().re_reviewing.insert(paper);
+                    self.sync_backlog();
+                }
+            }
+            Msg::Block(paper) => {
+                self.board_mut().papers.remove(&paper);
+                self.resort();
+                self.board_mut().multi_selected.remove(&paper);
+                if self.board().selected_paper == Some(paper) {
+                    self.board_mut().selected_paper = None;
+                }
+                let board = self.board().static_ins.config.board_name();
+                self.state
+                    .blocked
+                    .entry(board.to_owned())
+                    .or_default()
+                    .insert(paper);
+                self.state
+                    .save(&self.board().static_ins.config.state_path());
+            }
+            Msg::Unblock(paper) => {
+                let board = self.board().static_ins.config.board_name();
+                if let Some(pids) = self.state.blocked.get_mut(board) {
+                    pids.remove(&paper);
+                    if pids.is_empty() {
+                        self.state.blocked.remove(board);
+                    }
+                }
+                self.state
+                    .save(&self.board().static_ins.config.state_path());
+            }
+            Msg::FocusSearch => return text_input::focus(text_input::Id::new(SEARCH_INPUT_ID)),
+            Msg::SearchChanged(query) => {
+                self.search = Query::parse(&query);
+                self.search_query = query;
+            }
+            Msg::TaskPanicked(panic) => {
+                self.board_mut().last_error = Some(format!("Internal error: {panic}"));
+            }
+            Msg::DismissError => self.board_mut().last_error = None,
+            Msg::ResetSessionStats => {
+                self.accepted_this_session = 0;
+                self.rejected_this_session = 0;
+            }
+            Msg::CopyDetails(paper) => {
+                if let Some(paper) = self.board().papers.get(&paper) {
+                    return iced::clipboard::write(format!(
+                        "Name: {}\nEmail: {}\nTime: {}\n\n{}",
+                        paper.name,
+                        paper.email.as_deref().unwrap_or_default(),
+                        paper.time.to_rfc2822(),
+                        paper.info
+                    ));
+                }
+            }
+            Msg::Copy(text) => return iced::clipboard::write(text),
+            Msg::OpenEmail(email) => {
+                if let Err(err) = open::that_detached(format!("mailto:{email}")) {
+                    tracing::event!(tracing::Level::ERROR, "cannot open mail client: {err}");
+                }
+            }
+            Msg::OpenLink(url) => {
+                if let Err(err) = open::that_detached(&url) {
+                    tracing::event!(tracing::Level::ERROR, "cannot open {url}: {err}");
+                    self.board_mut().last_error = Some(format!("Cannot open {url}: {err}"));
+                }
+            }
+            Msg::BackendError(err) => self.board_mut().last_error = Some(err),
+            Msg::ToggleStaleOnly => self.stale_only = !self.stale_only,
+            Msg::SelectMatching { confirmed } => {
+                let pids: HashSet<u64> = self.visible_papers().iter().map(|e| e.pid).collect();
+                if !confirmed && pids.len() > self.board().static_ins.config.confirm_select_above {
+                    self.overlay = Some(Overlay::ConfirmSelect);
+                    return Command::none();
+                }
+                self.overlay = None;
+                self.board_mut().range_cursor = None;
+                self.board_mut().multi_selected = pids;
+            }
+            Msg::AcceptAllVisible { confirmed: false } => {
+                self.overlay = Some(Overlay::ConfirmAcceptAll);
+            }
+            Msg::AcceptAllVisible { confirmed: true } => {
+                self.overlay = None;
+                let pids: Vec<u64> = self
+                    .visible_papers()
+                    .iter()
+                    .filter(|e| e.processed.is_none())
+                    .map(|e| e.pid)
+                    .collect();
+                if pids.is_empty() {
+                    return Command::none();
+                }
+                self.board_mut().accept_batch = Some(AcceptBatch {
+                    pending: pids.iter().copied().collect(),
+                    total: pids.len(),
+                    failed: vec![],
+                });
+                return Command::batch(
+                    pids.into_iter()
+                        .map(|pid| self.decide(pid, Decision::Accept, None))
+                        .collect::<Vec<_>>(),
+                );
+            }
+            Msg::CycleFilter => self.filter = self.filter.next(),
+            Msg::ToggleExpanded(paper) => {
+                if self.board().expanded.contains(&paper) {
+                    self.board_mut().expanded.remove(&paper);
+                } else {
+                    self.board_mut().expanded.insert(paper);
+                }
+            }
+            Msg::ShowOverlay(overlay) => {
+                self.overlay = Some(overlay);
+                if overlay == Overlay::History && !self.board().history.loading {
+                    // Start over, decisions made since the last visit belong on top.
+                    self.board_mut().history = HistoryState::default();
+                    return self.update(Msg::LoadHistory);
+                }
+            }
+            Msg::LoadHistory if self.board().history.loading || self.board().history.exhausted => {}
+            Msg::LoadHistory => {
+                self.board_mut().history.loading = true;
+                let page = self.board().history.next_page;
+                let si = self.board().static_ins;
+                return Command::perform(
+                    async move {
+                        let span = tracing::span!(tracing::Level::INFO, "history page {page}");
+                        let _span = span.enter();
+
+                        if let Some(mock) = &si.mock {
+                            return Ok(mock.history(page, HISTORY_PAGE_SIZE).await);
+                        }
+
+                        let url = si.host.history.as_deref().unwrap_or_default();
+                        si.client
+                            .get(url)
+                            .query(&[("page", page as usize), ("size", HISTORY_PAGE_SIZE)])
+                            .send()
+                            .map_err(request_error)
+                            .and_then(|res| backend_error(si, res))
+                            .and_then(|res| res.json().map_err(request_error))
+                            .await
+                            .inspect_err(|err| tracing::event!(tracing::Level::ERROR, "{err}"))
+                    },
+                    Msg::HistoryLoaded,
+                );
+            }
+            Msg::HistoryLoaded(Ok(papers)) => {
+                self.board_mut().history.loading = false;
+                self.board_mut().history.next_page += 1;
+                self.board_mut().history.exhausted = papers.len() < HISTORY_PAGE_SIZE;
+                self.board_mut().history.papers.extend(papers);
+            }
+            Msg::HistoryLoaded(Err(err)) => {
+                self.board_mut().history.loading = false;
+                self.board_mut().last_error = Some(format!("Failed to load history: {err}"));
+            }
+            Msg::ListScrolled(viewport) => self.list_offset = viewport.absolute_offset().y,
+            Msg::HistoryScrolled(viewport) if viewport.relative_offset().y > 0.9 => {
+                return self.update(Msg::LoadHistory);
+            }
+            Msg::CloseOverlay => {
+                self.overlay = None;
+                self.board_mut().reject_pending.clear();
+            }
+            Msg::ImportPathChanged(path) => self.import.path = path,
+            Msg::PickImportFile => {
+                return Command::perform(
+                    rfd::AsyncFileDialog::new()
+                        .set_title("Import decisions")
+                        .add_filter("Decisions", &["csv", "json"])
+                        .pick_file(),
+                    |file| Msg::ImportFilePicked(file.map(|file| file.path().to_owned())),
+                );
+            }
+            Msg::ImportFilePicked(Some(path)) => self.import.path = path.display().to_string(),
+            // The dialog was dismissed.
+            Msg::ImportFilePicked(None) => {}
+            // Enter in the path field while a batch is still posting.
+            Msg::Import if self.import.running() => {}
+            Msg::Import => {
+                let decisions = match import::read(Path::new(&self.import.path)) {
+                    Ok(decisions) => decisions,
+                    Err(err) => {
+                        self.import.skipped = vec![err];
+                        return Command::none();
+                    }
+                };
+                self.import.results.clear();
+                self.import.skipped.clear();
+
+                let mut pids = vec![];
+                let mut seen = HashSet::new();
+                for (pid, decision) in decisions {
+                    let Some(paper) = self.board().papers.get(&pid) else {
+                        tracing::event!(tracing::Level::WARN, "importing unknown paper {pid}");
+                        self.import.skipped.push(format!("#{pid}: unknown paper"));
+                        continue;
+                    };
+                    // Decisions the server already has are not posted again.
+                    if paper.processed == Some(decision == Decision::Accept) {
+                        self.import.skipped.push(format!(
+                            "#{pid}: already {}",
+                            match decision {
+                                Decision::Accept => "accepted",
+                                Decision::Reject => "rejected",
+                            }
+                        ));
+                    } else if !seen.insert(pid) {
+                        self.import
+                            .skipped
+                            .push(format!("#{pid}: listed more than once"));
+                    } else if decision == Decision::Reject && !self.can_reject() {
+                        self.import
+                            .skipped
+                            .push(format!("#{pid}: rejecting needs reject_paper_mapping"));
+                    } else if decision == Decision::Reject
+                        && self.board().static_ins.config.require_reject_reason
+                    {
+                        self.import
+                            .skipped
+                            .push(format!("#{pid}: rejecting needs a reason"));
+                    } else {
+                        pids.push((pid, decision));
+                    }
+                }
+                self.import.total = pids.len();
+
+                let permits = Arc::new(tokio::sync::Semaphore::new(IMPORT_CONCURRENCY));
+                let si = self.board().static_ins;
+                return Command::batch(pids.into_iter().map(|(pid, decision)| {
+                    let permits = permits.clone();
+                    Command::perform(
+                        async move {
+                            let _permit = permits.acquire().await;
+                            // The same decision imported twice is the same request.
+                            let key =
+                                format!("import-{}-{pid}-{decision:?}", si.config.board_name());
+                            post_decision(si, pid, decision, None, Some(&key))
+                                .await
+                                .is_ok()
+                        },
+                        move |p| {
+                            Msg::Multi(vec![Msg::decided(pid, decision, p), Msg::Imported(pid, p)])
+                        },
+                    )
+                }));
+            }
+            Msg::Imported(pid, p) => self.import.results.push((pid, p)),
+            Msg::ExportPathChanged(path) => self.export.path = path,
+            Msg::ExportFromChanged(from) => self.export.from = from,
+            Msg::ExportToChanged(to) => self.export.to = to,
+            Msg::Export => {
+                self.export.result = Some(
+                    DateRange::parse(&self.export.from, &self.export.to).and_then(|range| {
+                        let papers: Vec<&Paper> = self
+                            .board()
+                            .order
+                            .iter()
+                            .filter_map(|pid| self.board().papers.get(pid))
+                            .filter(|paper| range.contains(paper))
+                            .collect();
+                        export::write(Path::new(&self.export.path), &papers).map(|_| papers.len())
+                    }),
+                );
+            }
+            Msg::CleanAccepted => {
+                let confirmed_only = self.board().static_ins.config.clean_confirmed_only;
+                let previous = self.board().order.clone();
+                self.board_mut()
+                    .papers
+                    .retain(|_, v| v.processed.is_none() || (confirmed_only && !v.confirmed));
+                self.resort();
+                return self.reconcile_selection(previous);
+            }
             Msg::Multi(vec) => {
                 let mut commands = Vec::with_capacity(vec.len());
                 for msg in vec {
@@ -250,47 +3117,164 @@ impl Application for App {
                 }
                 return Command::batch(commands);
             }
+            Msg::Event(iced::Event::Window(iced::window::Event::CloseRequested)) | Msg::Quit => {
+                self.closing = true;
+                if self.saved_layout != self.layout_changes {
+                    self.state
+                        .save(&self.board().static_ins.config.state_path());
+                }
+                return iced::window::close();
+            }
+            // Some window managers report minimizing as a resize to zero, which is no layout.
+            Msg::Event(iced::Event::Window(iced::window::Event::Resized { width, height }))
+                if width > 0 && height > 0 =>
+            {
+                self.window_size = (width, height);
+                return self.layout_changed();
+            }
             Msg::Event(iced::Event::Keyboard(iced::keyboard::Event::KeyPressed {
                 key_code,
-                ..
+                modifiers,
             })) => match key_code {
-                KeyCode::Up | KeyCode::K => {
-                    if let Some((v1, v2)) = self.selected_paper.zip(self.related_papers.0) {
-                        let mut papers: Vec<&Paper> = self.papers.values().collect();
-                        papers.sort_unstable_by_key(|paper| &paper.time);
-                        papers.reverse();
-                        return self.update(Msg::OpenPaper {
-                            before: papers
-                                .iter()
-                                .position(|e| e.pid == v2)
-                                .and_then(|pos| if pos == 0 { None } else { papers.get(pos - 1) })
-                                .map(|e| e.pid),
-                            target: v2,
-                            after: Some(v1),
-                        });
+                // The Nth canned reason while a reject waits for one.
+                _ if self.overlay == Some(Overlay::RejectReason) => {
+                    let digit = match key_code {
+                        KeyCode::Key1 | KeyCode::Numpad1 => 1,
+                        KeyCode::Key2 | KeyCode::Numpad2 => 2,
+                        KeyCode::Key3 | KeyCode::Numpad3 => 3,
+                        KeyCode::Key4 | KeyCode::Numpad4 => 4,
+                        KeyCode::Key5 | KeyCode::Numpad5 => 5,
+                        KeyCode::Key6 | KeyCode::Numpad6 => 6,
+                        KeyCode::Key7 | KeyCode::Numpad7 => 7,
+                        KeyCode::Key8 | KeyCode::Numpad8 => 8,
+                        KeyCode::Key9 | KeyCode::Numpad9 => 9,
+                        _ => return Command::none(),
+                    };
+                    return self.update(Msg::RejectWithReason(digit - 1));
+                }
+                // Papers hidden behind an overlay are not decided on blindly, only Escape closing
+                // it and Tab moving between its inputs get through.
+                _ if self.overlay.is_some()
+                    && !matches!(key_code, KeyCode::Escape | KeyCode::Tab) => {}
+                KeyCode::Up | KeyCode::K if modifiers.shift() => self.extend_range(false),
+                KeyCode::Down | KeyCode::J if modifiers.shift() => self.extend_range(true),
+                KeyCode::Up | KeyCode::K => return self.step_selection(false),
+                KeyCode::Down | KeyCode::J => return self.step_selection(true),
+                KeyCode::N if !modifiers.command() => {
+                    let papers = self.visible_papers();
+                    let pos = self
+                        .board()
+                        .selected_paper
+                        .and_then(|v| papers.iter().position(|e| e.pid == v));
+                    let pending = |e: &&&Paper| e.processed.is_none();
+                    // Going round the end of the list to the pending papers on the other side.
+                    let (above, below) = match pos {
+                        Some(pos) => (&papers[..pos], &papers[pos + 1..]),
+                        None => (&papers[..], &papers[..0]),
+                    };
+                    let next = if modifiers.shift() {
+                        above.iter().rev().chain(below.iter().rev()).find(pending)
+                    } else {
+                        below.iter().chain(above).find(pending)
+                    }
+                    .map(|e| e.pid);
+
+                    if let Some(pid) = next {
+                        return self.update(self.open_paper(pid));
+                    }
+                }
+                KeyCode::Z if modifiers.command() => {
+                    if let Some(paper) = self.board().undo_stack.back() {
+                        return self.update(Msg::Undo(paper.pid));
+                    }
+                }
+                KeyCode::Equals | KeyCode::Plus | KeyCode::NumpadAdd if modifiers.command() => {
+                    return self.update(Msg::Zoom(Some(TEXT_SCALE_STEP)));
+                }
+                KeyCode::Minus | KeyCode::NumpadSubtract if modifiers.command() => {
+                    return self.update(Msg::Zoom(Some(-TEXT_SCALE_STEP)));
+                }
+                KeyCode::Key0 | KeyCode::Numpad0 if modifiers.command() => {
+                    return self.update(Msg::Zoom(None));
+                }
+                KeyCode::Tab if modifiers.shift() => return iced::widget::focus_previous(),
+                KeyCode::Tab => return iced::widget::focus_next(),
+                KeyCode::C if !modifiers.command() => return self.update(Msg::CleanAccepted),
+                KeyCode::D if modifiers.command() && modifiers.shift() => {
+                    let dump = self.debug_dump();
+                    match serde_json::to_string_pretty(&dump)
+                        .map_err(|err| err.to_string())
+                        .and_then(|str| {
+                            std::fs::write(DEBUG_DUMP_PATH, str).map_err(|err| err.to_string())
+                        }) {
+                        Ok(()) => tracing::event!(
+                            tracing::Level::INFO,
+                            "dumped state to {DEBUG_DUMP_PATH}"
+                        ),
+                        Err(err) => tracing::event!(
+                            tracing::Level::ERROR,
+                            "cannot write {DEBUG_DUMP_PATH}: {err}, state: {dump}"
+                        ),
                     }
                 }
-                KeyCode::Down | KeyCode::J => {
-                    if let Some((v1, v2)) = self.selected_paper.zip(self.related_papers.1) {
-                        let mut papers: Vec<&Paper> = self.papers.values().collect();
-                        papers.sort_unstable_by_key(|paper| &paper.time);
-                        papers.reverse();
-                        return self.update(Msg::OpenPaper {
-                            after: papers
-                                .iter()
-                                .position(|e| e.pid == v2)
-                                .and_then(|pos| papers.get(pos + 1))
-                                .map(|e| e.pid),
-                            target: v2,
-                            before: Some(v1),
-                        });
+                KeyCode::R if !modifiers.command() => return self.update(Msg::Refresh),
+                KeyCode::D if !modifiers.command() => return self.update(Msg::ToggleDarkMode),
+                KeyCode::S if !modifiers.command() => return self.update(Msg::SwitchSplitAxis),
+                KeyCode::F if !modifiers.command() => return self.update(Msg::ToggleFocusMode),
+                // `?` on most layouts.
+                KeyCode::Slash if modifiers.shift() => self.overlay = Some(Overlay::Help),
+                // The modal closes overlays on its own.
+                KeyCode::Escape if self.overlay.is_none() => {
+                    self.board_mut().selected_paper = None;
+                    self.board_mut().related_papers = (None, None);
+                }
+                KeyCode::O if !modifiers.command() => {
+                    if let Some(url) = self
+                        .board()
+                        .selected_paper
+                        .and_then(|pid| self.board().papers.get(&pid))
+                        .and_then(|paper| paper.url.clone())
+                    {
+                        return self.update(Msg::OpenLink(url));
+                    }
+                }
+                KeyCode::B if !modifiers.command() => {
+                    if let Some(value) = self.board().selected_paper {
+                        return self.update(Msg::Block(value));
                     }
                 }
+                KeyCode::Enter | KeyCode::NumpadEnter
+                    if !self.board().multi_selected.is_empty() =>
+                {
+                    let board = self.board_mut();
+                    let pids: Vec<u64> = board
+                        .multi_selected
+                        .drain()
+                        .filter(|pid| board.papers.get(pid).is_some_and(|e| e.processed.is_none()))
+                        .collect();
+                    self.board_mut().range_cursor = None;
+                    return self.update(Msg::Multi(pids.into_iter().map(Msg::Accept).collect()));
+                }
                 KeyCode::Enter | KeyCode::NumpadEnter => {
-                    if let Some(value) = self.selected_paper {
+                    if let Some(value) = self.board().selected_paper {
                         return self.update(Msg::Accept(value));
                     }
                 }
+                KeyCode::Backspace if !self.board().multi_selected.is_empty() => {
+                    let board = self.board_mut();
+                    let pids: Vec<u64> = board
+                        .multi_selected
+                        .drain()
+                        .filter(|pid| board.papers.get(pid).is_some_and(|e| e.processed.is_none()))
+                        .collect();
+                    self.board_mut().range_cursor = None;
+                    return self.update(Msg::PromptReject(pids));
+                }
+                KeyCode::Backspace => {
+                    if let Some(value) = self.board().selected_paper {
+                        return self.update(Msg::PromptReject(vec![value]));
+                    }
+                }
                 _ => (),
             },
             _ => (),
@@ -298,231 +3282,746 @@ impl Application for App {
 
         iced::Command::none()
     }
+}
+
+impl Application for App {
+    type Executor = iced_futures::backend::native::tokio::Executor;
+
+    type Message = Msg;
+
+    type Theme = iced::Theme;
+
+    type Flags = Flags;
+
+    fn new(flags: Self::Flags) -> (Self, iced::Command<Self::Message>) {
+        let (configs, mut config_error) = match flags {
+            Flags::Config(boards) => (boards, None),
+            Flags::Error(err) => (vec![Config::default()], Some(err)),
+        };
+        let metrics: &'static Metrics = Box::leak(Box::default());
+        let mut boards = vec![];
+        for config in configs {
+            let (static_ins, err) = StaticIns::build(config, metrics);
+            config_error = config_error.or(err);
+            boards.push(&*Box::leak(Box::new(static_ins)));
+        }
+        let broken = config_error.is_some();
+        // Settings of the window rather than a board are taken from the first one.
+        let flags = &boards[0].config;
+        let on_start = match flags.on_start {
+            StartAction::Refresh => Command::none(),
+            StartAction::SelectNewest => Command::perform(async {}, |_| Msg::SelectNewest),
+            StartAction::FocusMode => Command::perform(async {}, |_| Msg::ToggleFocusMode),
+        };
+        let state = State::load(&flags.state_path());
+        let restore_size = state
+            .window_size
+            .map_or(Command::none(), |(width, height)| {
+                iced::window::resize(iced::Size::new(width, height))
+            });
+        let serve_metrics = flags.metrics_port.map_or(Command::none(), |port| {
+            Command::perform(metrics.serve(port), |_| Msg::Noop)
+        });
+        // Each board refreshes on its own timer.
+        let refresh_loops = (0..boards.len()).map(|index| {
+            Command::perform(async {}, move |_| {
+                Msg::RefreshLoop(Duration::ZERO).on_board(index)
+            })
+        });
+        let boards = boards
+            .into_iter()
+            .map(|static_ins| Board::new(static_ins, &state, broken))
+            .collect();
+        let mut app = Self {
+            boards,
+            active_board: 0,
+            split_0_pos: Some(state.split_pos.unwrap_or(250)),
+            sort_key: state.sort,
+            sort_ascending: state.sort_ascending,
+            nerd_font: Font::MONOSPACE,
+            dark_mode: state.dark_mode,
+            split_axis: if state.split_horizontal {
+                iced_aw::split::Axis::Horizontal
+            } else {
+                iced_aw::split::Axis::Vertical
+            },
+            display_bg: true,
+            focus_mode: false,
+            window_size: state.window_size.unwrap_or((1200, 800)),
+            text_scale: state
+                .text_scale
+                .filter(|scale| TEXT_SCALE_RANGE.contains(scale))
+                .unwrap_or(1.0),
+            list_offset: 0.0,
+            closing: false,
+            state,
+            layout_changes: 0,
+            saved_layout: 0,
+            overlay: None,
+            reject_note: String::new(),
+            import: ImportState::default(),
+            export: ExportState::default(),
+            search_query: String::new(),
+            search: Query::default(),
+            stale_only: false,
+            filter: Filter::All,
+            accepted_this_session: 0,
+            rejected_this_session: 0,
+            config_error,
+        };
+        app.resort();
+        (
+            app,
+            if broken {
+                // Nothing to talk to, only the error is shown.
+                Command::none()
+            } else {
+                Command::batch(refresh_loops.chain([
+                    iced::font::load(NERD_FONT).map(Msg::FontLoaded),
+                    on_start,
+                    serve_metrics,
+                    restore_size,
+                ]))
+            },
+        )
+    }
+
+    #[inline]
+    fn title(&self) -> String {
+        format!(
+            "{}SubBoard{}",
+            match self
+                .board()
+                .papers
+                .values()
+                .filter(|e| e.processed.is_none())
+                .count()
+            {
+                count @ 1.. if self.board().static_ins.config.pending_badge =>
+                    format!("({count}) "),
+                _ => Default::default(),
+            },
+            if let Some(value) = self
+                .board()
+                .selected_paper
+                .and_then(|v| self.board().papers.get(&v))
+            {
+                format!(" - Paper from {}", value.name)
+            } else {
+                Default::default()
+            }
+        )
+    }
+
+    fn update(&mut self, message: Self::Message) -> iced::Command<Self::Message> {
+        let (index, message) = match message {
+            Msg::OnBoard(index, message) => (index, *message),
+            message => (self.active_board, message),
+        };
+        // Only the error is shown, shortcuts and timers have nothing to act on and the state
+        // file of a broken configuration is left alone.
+        if self.config_error.is_some() {
+            return match message {
+                Msg::Quit
+                | Msg::Event(iced::Event::Window(iced::window::Event::CloseRequested)) => {
+                    iced::window::close()
+                }
+                _ => Command::none(),
+            };
+        }
+        match message {
+            Msg::SwitchBoard(index) => {
+                self.active_board = index;
+                self.resort();
+                Command::none()
+            }
+            // One timer serves the retries of every board.
+            Msg::Tick(now) => Command::batch(
+                (0..self.boards.len())
+                    .filter(|&index| self.boards[index].retry_at.is_some_and(|at| at <= now))
+                    .collect::<Vec<_>>()
+                    .into_iter()
+                    .map(|index| self.update(Msg::OnBoard(index, Box::new(Msg::RetryNow)))),
+            ),
+            message => {
+                // Results of what the message starts come back to the same board.
+                let active = std::mem::replace(&mut self.active_board, index);
+                let command = self.update_board(message);
+                self.active_board = active;
+                command.map(move |message| message.on_board(index))
+            }
+        }
+    }
 
     fn view(&self) -> iced::Element<'_, Self::Message, iced::Renderer<Self::Theme>> {
+        if let Some(err) = self.config_error.as_deref() {
+            return container(
+                container(
+                    Column::new()
+                        .spacing(15)
+                        .push(Text::new("SubBoard could not start").size(22))
+                        .push(Text::new(err))
+                        .push(
+                            button(Text::new("Quit"))
+                                .style(theme::Button::Destructive)
+                                .on_press(Msg::Quit),
+                        ),
+                )
+                .max_width(600)
+                .padding(20)
+                .style(theme::Container::Box),
+            )
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .center_x()
+            .center_y()
+            .into();
+        }
+
         let mut left = Column::new();
 
+        if self.boards.len() > 1 {
+            left = left.push(self.tabs_view());
+        }
+
         {
             let mut bar = Row::new().height(30).width(Length::Fill);
 
             bar = bar.push(
-                Text::new("   PAPERS")
+                Text::new(format!("   PAPERS   {}", self.paper_counts()))
                     .height(30)
                     .width(Length::Fill)
                     .horizontal_alignment(iced::alignment::Horizontal::Left)
                     .vertical_alignment(iced::alignment::Vertical::Center)
                     .style(Color::new(0.5, 0.5, 0.5, 1.0)),
             );
+            // Who the decisions are posted as, so a shared machine shows whose session it is.
+            let mut operator_width = 0.0;
+            if let Some(operator) = self.board().static_ins.config.operator() {
+                operator_width = 30.0 + operator.chars().count() as f32 * 13.5 * AVERAGE_CHAR_WIDTH;
+                bar = bar.push(
+                    Row::new()
+                        .height(30)
+                        .spacing(3)
+                        .padding([0, 5])
+                        .align_items(iced::Alignment::Center)
+                        .push(
+                            Text::new("")
+                                .size(13.5)
+                                .font(self.nerd_font)
+                                .style(Color::new(0.5, 0.5, 0.5, 1.0)),
+                        )
+                        .push(
+                            Text::new(operator)
+                                .size(13.5)
+                                .style(Color::new(0.5, 0.5, 0.5, 1.0)),
+                        )
+                        .width(operator_width),
+                );
+            }
+            bar = bar.push(
+                button(
+                    Row::new()
+                        .spacing(3)
+                        .push(Text::new(self.sort_key.label()).size(13.5))
+                        .push(
+                            Text::new(if self.sort_ascending { "" } else { "" })
+                                .size(13.5)
+                                .font(self.nerd_font),
+                        ),
+                )
+                .width(SORT_BUTTON_WIDTH)
+                .height(30)
+                .style(theme::Button::Text)
+                .on_press(Msg::SetSort(
+                    if self.sort_ascending == self.sort_key.ascending_first() {
+                        self.sort_key
+                    } else {
+                        self.sort_key.next()
+                    },
+                )),
+            );
 
-            bar = bar
-                .push(
-                    button(
-                        Text::new(match self.split_axis {
-                            iced_aw::split::Axis::Vertical => "",
-                            iced_aw::split::Axis::Horizontal => "",
-                        })
-                        .width(23.5)
-                        .height(30)
-                        .size(13.5)
-                        .horizontal_alignment(iced::alignment::Horizontal::Center)
-                        .style(Color::new(0.5, 0.5, 0.5, 1.0))
-                        .font(self.nerd_font),
-                    )
-                    .style(theme::Button::Text)
-                    .on_press(Msg::SwitchSplitAxis),
-                )
-                .push(
-                    button(
-                        Text::new("")
-                            .width(23.5)
-                            .height(30)
-                            .size(13.5)
-                            .horizontal_alignment(iced::alignment::Horizontal::Center)
-                            .style(Color::new(0.5, 0.5, 0.5, 1.0))
-                            .font(self.nerd_font),
-                    )
-                    .style(theme::Button::Text)
-                    .on_press(Msg::ToggleDarkMode),
-                )
-                .push(
-                    button(
-                        Text::new("")
-                            .width(23.5)
+            let mut secondary = vec![
+                (
+                    match self.split_axis {
+                        iced_aw::split::Axis::Vertical => "",
+                        iced_aw::split::Axis::Horizontal => "",
+                    },
+                    "Switch layout",
+                    Msg::SwitchSplitAxis,
+                ),
+                ("", "Toggle dark mode", Msg::ToggleDarkMode),
+                ("", "Clean accepted", Msg::CleanAccepted),
+                ("", "Blocked papers", Msg::ShowOverlay(Overlay::Blocklist)),
+                ("", "Import decisions", Msg::ShowOverlay(Overlay::Import)),
+                ("", "Export papers", Msg::ShowOverlay(Overlay::Export)),
+                ("", self.filter.next_label(), Msg::CycleFilter),
+                ("", "Keyboard shortcuts", Msg::ShowOverlay(Overlay::Help)),
+            ];
+            if self.filtering() {
+                secondary.push((
+                    "",
+                    "Select all matching",
+                    Msg::SelectMatching { confirmed: false },
+                ));
+            }
+            if self.visible_papers().iter().any(|e| e.processed.is_none()) {
+                secondary.push((
+                    "",
+                    "Accept all shown",
+                    Msg::AcceptAllVisible { confirmed: false },
+                ));
+            }
+            if self.board().static_ins.config.stale_after_hours.is_some() {
+                secondary.push((
+                    "",
+                    if self.stale_only {
+                        "Show all papers"
+                    } else {
+                        "Stale papers only"
+                    },
+                    Msg::ToggleStaleOnly,
+                ));
+            }
+            if self.board().static_ins.host.history.is_some()
+                || self.board().static_ins.mock.is_some()
+            {
+                secondary.push(("", "History", Msg::ShowOverlay(Overlay::History)));
+            }
+            if self.board().static_ins.config.error_history_len > 0 {
+                secondary.push(("", "Error history", Msg::ShowOverlay(Overlay::Errors)));
+            }
+
+            if self.bar_width()
+                >= BAR_LABEL_WIDTH
+                    + operator_width
+                    + SORT_BUTTON_WIDTH
+                    + ICON_BUTTON_WIDTH * (secondary.len() + 1) as f32
+            {
+                for (glyph, _, msg) in secondary {
+                    bar = bar.push(self.icon_button(glyph, msg));
+                }
+            } else {
+                bar = bar.push(
+                    MenuBar::new(vec![MenuTree::with_children(
+                        Text::new("")
+                            .width(ICON_BUTTON_WIDTH)
                             .height(30)
                             .size(13.5)
                             .horizontal_alignment(iced::alignment::Horizontal::Center)
+                            .vertical_alignment(iced::alignment::Vertical::Center)
                             .style(Color::new(0.5, 0.5, 0.5, 1.0))
                             .font(self.nerd_font),
-                    )
-                    .style(theme::Button::Text)
-                    .on_press(Msg::CleanAccepted),
+                        secondary
+                            .into_iter()
+                            .map(|(glyph, label, msg)| {
+                                MenuTree::new(
+                                    button(
+                                        Row::new()
+                                            .push(Text::new(glyph).font(self.nerd_font))
+                                            .push(horizontal_space(7.5))
+                                            .push(Text::new(label)),
+                                    )
+                                    .width(Length::Fill)
+                                    .style(theme::Button::Text)
+                                    .on_press(msg),
+                                )
+                            })
+                            .collect(),
+                    )])
+                    .item_width(ItemWidth::Uniform(180)),
                 );
+            }
 
-            if Arc::strong_count(&self.refresh_count) == 1 {
+            if self.refreshing() {
+                // Pulses in place of the refresh button, one beat per second.
+                let beat = chrono::Utc::now().timestamp_subsec_millis() as f32 / 1000.0;
+                let alpha = if self.board().static_ins.config.reduced_motion {
+                    1.0
+                } else {
+                    0.6 + 0.4 * (beat * std::f32::consts::TAU).cos()
+                };
                 bar = bar.push(
-                    button(
-                        Text::new("")
-                            .width(23.5)
+                    container(
+                        Text::new("")
+                            .width(ICON_BUTTON_WIDTH)
                             .height(30)
                             .size(13.5)
                             .horizontal_alignment(iced::alignment::Horizontal::Center)
-                            .style(Color::new(0.5, 0.5, 0.5, 1.0))
+                            .style(Color::new(0.5, 0.5, 0.5, alpha))
                             .font(self.nerd_font),
                     )
-                    .style(theme::Button::Text)
-                    .on_press(Msg::Refresh),
+                    .padding(5),
                 );
+            } else {
+                bar = bar.push(self.icon_button("", Msg::Refresh));
             }
 
             left = left.push(bar);
         }
 
-        {
-            let mut down = Column::new().width(Length::Fill);
+        left = left.push(
+            container(
+                Row::new()
+                    .align_items(iced::Alignment::Center)
+                    .push(
+                        button(
+                            Text::new("")
+                                .size(13.5)
+                                .style(Color::new(0.5, 0.5, 0.5, 1.0))
+                                .font(self.nerd_font),
+                        )
+                        .padding([0, 5, 0, 0])
+                        .style(theme::Button::Text)
+                        .on_press(Msg::FocusSearch),
+                    )
+                    .push(
+                        text_input(
+                            "Search, e.g. name:alice email:gmail.com",
+                            &self.search_query,
+                        )
+                        .id(text_input::Id::new(SEARCH_INPUT_ID))
+                        .on_input(Msg::SearchChanged)
+                        .style(theme::TextInput::Custom(Box::new(style::FocusRing)))
+                        .size(13.5)
+                        .padding(5),
+                    ),
+            )
+            .padding([0, 10, 5, 10]),
+        );
 
-            let mut papers: Vec<&Paper> = self.papers.values().collect();
-            papers.sort_unstable_by_key(|paper| &paper.time);
-            papers.reverse();
+        if let Some(at) = self.board().retry_at {
+            let secs = at.saturating_duration_since(Instant::now()).as_secs();
+            left = left.push(
+                container(
+                    Row::new()
+                        .height(30)
+                        .push(
+                            Text::new(format!("   reconnecting in {secs}s…"))
+                                .height(30)
+                                .width(Length::Fill)
+                                .vertical_alignment(iced::alignment::Vertical::Center)
+                                .style(Color::new(0.5, 0.5, 0.5, 1.0)),
+                        )
+                        .push(
+                            button(
+                                Text::new("retry now")
+                                    .height(30)
+                                    .vertical_alignment(iced::alignment::Vertical::Center),
+                            )
+                            .style(theme::Button::Text)
+                            .on_press(Msg::RetryNow),
+                        ),
+                )
+                .style(theme::Container::Box),
+            );
+        }
+
+        {
+            let papers = self.visible_papers();
+            if papers.is_empty() {
+                left = left.push(self.empty_list_view());
+            } else {
+                let mut down = Column::new().width(Length::Fill);
 
-            let mut before = None;
-            let mut after;
+                // Headers start a group whenever the local submission date changes.
+                let mut rows = Vec::with_capacity(papers.len());
+                let mut date = None;
+                for (index, paper) in papers.iter().enumerate() {
+                    if self.groups_by_date() {
+                        let day = paper.time.with_timezone(&chrono::Local).date_naive();
+                        if date != Some(day) {
+                            rows.push(ListRow::Header(day));
+                            date = Some(day);
+                        }
+                    }
+                    rows.push(ListRow::Paper(index));
+                }
 
-            for paper in papers.iter().copied().enumerate() {
-                after = papers.get(paper.0 + 1).copied().map(|e| e.pid);
+                // Long lists only build the rows around the viewport, with spacers for the rest.
+                // Expanded rows are taller than assumed, which only shifts the window a little.
+                let row_height = self.board().static_ins.config.list_row_height + LIST_ROW_PADDING;
+                let window = if papers.len() > self.board().static_ins.config.virtualize_above {
+                    let first = ((self.list_offset / row_height) as usize).saturating_sub(OVERSCAN);
+                    let height = self.window_size.1 as f32 / self.text_scale;
+                    let len = (height / row_height) as usize + 2 * OVERSCAN;
+                    first.min(rows.len())..(first + len).min(rows.len())
+                } else {
+                    0..rows.len()
+                };
+                down = down.push(vertical_space(window.start as f32 * row_height));
 
-                down = down.push(
-                    button(
-                        container({
-                            let mut row = Row::new().height(18.5).push(
-                                Text::new(format!(" {}: {}", paper.1.name, paper.1.info))
-                                    .width(Length::Fill)
-                                    .horizontal_alignment(iced::alignment::Horizontal::Left)
-                                    .vertical_alignment(iced::alignment::Vertical::Center),
+                for row in &rows[window.clone()] {
+                    let index = match row {
+                        ListRow::Header(date) => {
+                            // Inset like the text of the row buttons below.
+                            down = down.push(
+                                container(
+                                    Text::new(
+                                        self.board()
+                                            .static_ins
+                                            .config
+                                            .time_format
+                                            .format_date(*date),
+                                    )
+                                    .size(12)
+                                    .height(row_height)
+                                    .vertical_alignment(iced::alignment::Vertical::Bottom)
+                                    .style(Color::new(0.5, 0.5, 0.5, 1.0)),
+                                )
+                                .padding([0, 5]),
                             );
+                            continue;
+                        }
+                        ListRow::Paper(index) => *index,
+                    };
+                    // Neighbours skip headers, they are the papers around this one.
+                    let paper = (index, papers[index]);
+                    let before = index.checked_sub(1).map(|i| papers[i].pid);
+                    let after = papers.get(index + 1).map(|e| e.pid);
 
-                            if let Some(p) = paper.1.processed {
-                                row = row.push(
-                                    Text::new("")
-                                        .size(10)
-                                        .width(18.5)
-                                        .height(18.5)
-                                        .horizontal_alignment(iced::alignment::Horizontal::Center)
-                                        .vertical_alignment(iced::alignment::Vertical::Center)
-                                        .font(self.nerd_font)
-                                        .style(if p {
-                                            self.theme().palette().success
-                                        } else {
-                                            self.theme().palette().danger
-                                        }),
-                                );
-                            }
+                    down = down.push(
+                        button(
+                            container({
+                                let expanded = self.board().expanded.contains(&paper.1.pid);
+                                let mut row = Row::new()
+                                    .height(self.board().static_ins.config.list_row_height);
+                                // Unparsable colors still mark the paper as colored, uncolored
+                                // rows keep the room so titles line up.
+                                let swatch = paper.1.color.as_ref().map(|color| {
+                                    HexColor::from_str(color)
+                                        .map_or(Color::new(0.5, 0.5, 0.5, 1.0), style::from_hex)
+                                });
+                                row =
+                                    row.push(
+                                        container(
+                                            container(horizontal_space(SWATCH_SIZE))
+                                                .width(SWATCH_SIZE)
+                                                .height(SWATCH_SIZE)
+                                                .style(swatch.map_or(
+                                                    theme::Container::Transparent,
+                                                    |color| style::filled(color, None),
+                                                )),
+                                        )
+                                        .height(self.board().static_ins.config.list_row_height)
+                                        .padding([0, 2, 0, 0])
+                                        .center_y(),
+                                    );
+                                row = row
+                                    .push(
+                                        button(
+                                            Text::new(if expanded { "" } else { "" })
+                                                .size(10)
+                                                .height(
+                                                    self.board().static_ins.config.list_row_height,
+                                                )
+                                                .vertical_alignment(
+                                                    iced::alignment::Vertical::Center,
+                                                )
+                                                .style(Color::new(0.5, 0.5, 0.5, 1.0))
+                                                .font(self.nerd_font),
+                                        )
+                                        .padding([0, 2])
+                                        .style(theme::Button::Text)
+                                        .on_press(Msg::ToggleExpanded(paper.1.pid)),
+                                    )
+                                    .push(
+                                        Text::new(ellipsize(
+                                            &format!("{}: {}", paper.1.name, paper.1.info),
+                                            self.list_row_columns(),
+                                        ))
+                                        .size(self.board().static_ins.config.list_font_size)
+                                        .width(Length::Fill)
+                                        .horizontal_alignment(iced::alignment::Horizontal::Left)
+                                        .vertical_alignment(iced::alignment::Vertical::Center),
+                                    );
 
-                            row
-                        })
-                        .style(
-                            if self.selected_paper.map_or(false, |e| paper.1.pid == e) {
-                                theme::Container::Box
-                            } else {
-                                theme::Container::Transparent
-                            },
-                        ),
-                    )
-                    .style(theme::Button::Text)
-                    .on_press(Msg::OpenPaper {
-                        before,
-                        target: paper.1.pid,
-                        after,
-                    }),
-                );
+                                if self.board().re_reviewing.contains(&paper.1.pid) {
+                                    row = row.push(
+                                        Text::new("RE-REVIEW")
+                                            .size(10)
+                                            .height(self.board().static_ins.config.list_row_height)
+                                            .vertical_alignment(iced::alignment::Vertical::Center)
+                                            .style(self.theme().palette().primary),
+                                    );
+                                }
+
+                                if !paper.1.attachments.is_empty() {
+                                    row = row.push(
+                                        Text::new(format!("{}", paper.1.attachments.len()))
+                                            .size(10)
+                                            .height(self.board().static_ins.config.list_row_height)
+                                            .vertical_alignment(iced::alignment::Vertical::Center)
+                                            .style(Color::new(0.5, 0.5, 0.5, 1.0))
+                                            .font(self.nerd_font),
+                                    );
+                                }
+
+                                if self.is_stale(paper.1) {
+                                    row = row.push(
+                                        Text::new("STALE")
+                                            .size(10)
+                                            .height(self.board().static_ins.config.list_row_height)
+                                            .vertical_alignment(iced::alignment::Vertical::Center)
+                                            .style(self.theme().palette().danger),
+                                    );
+                                }
+
+                                if let Some(p) = paper.1.processed {
+                                    row = row.push(
+                                        Text::new("")
+                                            .size(10)
+                                            .width(self.board().static_ins.config.list_row_height)
+                                            .height(self.board().static_ins.config.list_row_height)
+                                            .horizontal_alignment(
+                                                iced::alignment::Horizontal::Center,
+                                            )
+                                            .vertical_alignment(iced::alignment::Vertical::Center)
+                                            .font(self.nerd_font)
+                                            .style(self.decision_color(p)),
+                                    );
+                                }
+
+                                let mut col = Column::new().push(row);
+                                if expanded {
+                                    col =
+                                        col.push(
+                                            container(Text::new(&paper.1.info).size(
+                                                self.board().static_ins.config.list_font_size,
+                                            ))
+                                            .padding([2, 5, 5, 16]),
+                                        );
+                                }
+                                col
+                            })
+                            .style({
+                                let focused = self.board().selected_paper == Some(paper.1.pid);
+                                // Decisions are mostly made on the selected paper, so the
+                                // flash shows through the selection too.
+                                let flash = self.flash_alpha(paper.1).map(|alpha| Color {
+                                    a: alpha,
+                                    ..self.decision_color(paper.1.processed == Some(true))
+                                });
+                                if focused || self.board().multi_selected.contains(&paper.1.pid) {
+                                    style::selected_row(
+                                        self.board()
+                                            .static_ins
+                                            .config
+                                            .selection_color
+                                            .map(style::from_hex),
+                                        focused,
+                                        flash,
+                                    )
+                                } else if let Some(flash) = flash {
+                                    style::filled(flash, None)
+                                } else {
+                                    theme::Container::Transparent
+                                }
+                            }),
+                        )
+                        .style(theme::Button::Text)
+                        .on_press(Msg::OpenPaper {
+                            before,
+                            target: paper.1.pid,
+                            after,
+                        }),
+                    );
+                }
 
-                before = Some(paper.1.pid);
+                down = down.push(vertical_space(
+                    (rows.len() - window.end) as f32 * row_height,
+                ));
+                left = left.push(
+                    Scrollable::new(down)
+                        .height(Length::Fill)
+                        .on_scroll(Msg::ListScrolled),
+                );
             }
+        }
 
-            left = left.push(Scrollable::new(down).height(Length::Fill));
+        if self.accepted_this_session > 0 || self.rejected_this_session > 0 {
+            left = left.push(self.session_stats_view());
         }
 
         let mut right = Column::new().height(Length::Fill).width(Length::Fill);
+        if let Some(err) = self.board().last_error.as_deref() {
+            right = right.push(vertical_space(15)).push(
+                container(
+                    Row::new()
+                        .push(
+                            Text::new(err)
+                                .width(Length::Fill)
+                                .vertical_alignment(iced::alignment::Vertical::Center),
+                        )
+                        .push(
+                            button(Text::new("").font(self.nerd_font))
+                                .style(theme::Button::Text)
+                                .on_press(Msg::DismissError),
+                        ),
+                )
+                .padding([0, 0, 0, 10])
+                .width(Length::Fill)
+                .style(theme::Container::Custom(Box::new(
+                    |theme: &iced::Theme| iced::widget::container::Appearance {
+                        text_color: Some(Color::WHITE),
+                        background: Some(iced::Background::Color(theme.palette().danger)),
+                        border_radius: Default::default(),
+                        border_width: 0.,
+                        border_color: Default::default(),
+                    },
+                ))),
+            );
+        }
         if let Some(paper) = self
+            .board()
             .selected_paper
-            .and_then(|value| self.papers.get(&value))
+            .and_then(|value| self.board().papers.get(&value))
         {
-            const YELLOW: HexColor = HexColor {
-                r: 255,
-                g: 255,
-                b: 204,
-                a: u8::MAX,
-            };
+            // Missing or unparsable colors get no chip rather than a made-up one.
             let hex_color = paper
                 .color
                 .as_ref()
-                .and_then(|str| HexColor::from_str(str).ok())
-                .unwrap_or(YELLOW);
-
-            right = right.push(
-                Scrollable::new({
-                    let mut col = Column::new()
-                        .push(vertical_space(15))
-                        .push(
-                            Row::new().push(
-                                container(Text::new(format!("  {}  ", paper.info)).size(18.5))
-                                    .style(if self.display_bg {
-                                        theme::Container::Custom(Box::new(move |_: &_| {
-                                            iced::widget::container::Appearance {
-                                                text_color: Some(color!(000000)),
-                                                background: Some(iced::Background::Color(
-                                                    Color::from_rgb8(
-                                                        hex_color.r,
-                                                        hex_color.g,
-                                                        hex_color.b,
-                                                    ),
-                                                )),
-                                                border_radius: Default::default(),
-                                                border_width: 0.,
-                                                border_color: Default::default(),
-                                            }
-                                        }))
-                                    } else {
-                                        theme::Container::Transparent
-                                    })
-                                    .width(Length::Fill),
-                            ),
-                        )
-                        .push(vertical_space(15))
-                        .push(
-                            Row::new()
-                                .push(Text::new("").font(self.nerd_font))
-                                .push(horizontal_space(3.5))
-                                .push(Text::new(&paper.name)),
-                        );
-
-                    if let Some(email) = paper.email.as_deref() {
-                        col = col.push(
-                            Row::new()
-                                .push(Text::new("").font(self.nerd_font))
-                                .push(horizontal_space(3.5))
-                                .push(Text::new(email)),
-                        );
-                    }
-
-                    col.push(
-                        Text::new(paper.time.to_rfc2822()).style(Color::new(0.5, 0.5, 0.5, 1.)),
-                    )
-                })
-                .height(Length::Fill),
-            );
+                .and_then(|str| HexColor::from_str(str).ok());
 
-            if paper.processed.is_none() {
+            let mut actions = paper.processed.is_none().then(|| {
                 let mut row = Row::new().height(35).push(
                     button(
                         Text::new("Accept")
                             .horizontal_alignment(iced::alignment::Horizontal::Center),
                     )
                     .width(Length::Fill)
-                    .style(theme::Button::Positive)
+                    .style(
+                        self.board()
+                            .static_ins
+                            .config
+                            .accept_color
+                            .map_or(theme::Button::Positive, |color| {
+                                style::filled_button(style::from_hex(color))
+                            }),
+                    )
                     .on_press(Msg::Accept(paper.pid)),
                 );
-
-                row = row.push(
+                if self.can_reject() {
+                    row = row.push(
+                        button(
+                            Text::new("Reject")
+                                .horizontal_alignment(iced::alignment::Horizontal::Center),
+                        )
+                        .width(Length::Fill)
+                        .style(
+                            self.board()
+                                .static_ins
+                                .config
+                                .reject_color
+                                .map_or(theme::Button::Destructive, |color| {
+                                    style::filled_button(style::from_hex(color))
+                                }),
+                        )
+                        .on_press(Msg::PromptReject(vec![paper.pid])),
+                    );
+                }
+                row.push(self.copy_details_button(paper.pid)).push(
                     button(
                         Text::new("")
                             .size(16.5)
@@ -534,64 +4033,437 @@ impl Application for App {
                             .font(self.nerd_font),
                     )
                     .style(theme::Button::Text)
-                    .on_press(Msg::ToggleBg),
+                    .on_press_maybe(hex_color.is_some().then_some(Msg::ToggleBg)),
+                )
+            });
+            if paper.processed.is_some() {
+                actions = Some(
+                    Row::new()
+                        .height(35)
+                        .spacing(10)
+                        .push(
+                            button(
+                                Text::new("Undo")
+                                    .horizontal_alignment(iced::alignment::Horizontal::Center),
+                            )
+                            .width(Length::Fill)
+                            .style(theme::Button::Secondary)
+                            .on_press_maybe(
+                                (self.board().static_ins.mock.is_some()
+                                    || self.board().static_ins.host.unprocess.is_some())
+                                .then_some(Msg::Undo(paper.pid)),
+                            ),
+                        )
+                        .push(
+                            button(
+                                Text::new("Re-review")
+                                    .horizontal_alignment(iced::alignment::Horizontal::Center),
+                            )
+                            .width(Length::Fill)
+                            .style(theme::Button::Secondary)
+                            .on_press(Msg::ReReview(paper.pid)),
+                        )
+                        .push(self.copy_details_button(paper.pid)),
                 );
+            }
+            if let Some(row) = actions
+                .take_if(|_| self.board().static_ins.config.action_buttons == ActionButtons::Top)
+            {
+                right = right.push(vertical_space(15)).push(row);
+            }
+
+            right = right.push(
+                Scrollable::new({
+                    let mut col = Column::new().push(vertical_space(15));
+
+                    if let Some(accepted) = paper.processed {
+                        let mut status = Row::new()
+                            .spacing(5)
+                            .push(
+                                Text::new(if accepted { "" } else { "" })
+                                    .font(self.nerd_font)
+                                    .style(self.decision_color(accepted)),
+                            )
+                            .push(
+                                Text::new(if accepted { "Accepted" } else { "Rejected" })
+                                    .style(self.decision_color(accepted)),
+                            );
+                        if let Some(time) = &paper.processed_at {
+                            status = status.push(
+                                Text::new(format!(
+                                    "at {}",
+                                    self.board().static_ins.config.time_format.format(time)
+                                ))
+                                .style(Color::new(0.5, 0.5, 0.5, 1.)),
+                            );
+                        }
+                        col = col.push(status).push(vertical_space(15));
+                    }
+
+                    for section in &self.board().static_ins.detail_sections {
+                        match section {
+                            DetailSection::Info => {
+                                col = col
+                                    .push(
+                                        Row::new().width(Length::Fill).push(
+                                            container(self.info_view(&paper.info))
+                                                .style(match hex_color {
+                                                    Some(hex_color) if self.display_bg => {
+                                                        style::filled(
+                                                            Color::from_rgb8(
+                                                                hex_color.r,
+                                                                hex_color.g,
+                                                                hex_color.b,
+                                                            ),
+                                                            Some(color!(000000)),
+                                                        )
+                                                    }
+                                                    _ => theme::Container::Transparent,
+                                                })
+                                                .width(Length::Fill),
+                                        ),
+                                    )
+                                    .push(vertical_space(15))
+                            }
+                            DetailSection::Name => {
+                                col = col.push(
+                                    Row::new()
+                                        .push(Text::new("").font(self.nerd_font))
+                                        .push(horizontal_space(3.5))
+                                        .push(Text::new(&paper.name))
+                                        .push(self.copy_button(paper.name.clone())),
+                                )
+                            }
+                            DetailSection::Email => {
+                                if let Some(email) = paper.email.as_deref() {
+                                    col = col.push(
+                                        Row::new()
+                                            .push(Text::new("").font(self.nerd_font))
+                                            .push(horizontal_space(3.5))
+                                            .push(
+                                                button(Text::new(email))
+                                                    .padding(0)
+                                                    .style(theme::Button::Text)
+                                                    .on_press(Msg::OpenEmail(email.to_owned())),
+                                            )
+                                            .push(self.copy_button(email.to_owned())),
+                                    );
+                                }
+                            }
+                            DetailSection::Attachments if !paper.attachments.is_empty() => {
+                                col = col.push(
+                                    Row::new()
+                                        .push(Text::new("").font(self.nerd_font))
+                                        .push(horizontal_space(3.5))
+                                        .push(
+                                            Text::new(match paper.attachments.len() {
+                                                1 => "1 attachment".to_owned(),
+                                                count => format!("{count} attachments"),
+                                            })
+                                            .style(Color::new(0.5, 0.5, 0.5, 1.)),
+                                        ),
+                                );
+                                for attachment in &paper.attachments {
+                                    let label = attachment
+                                        .trim_end_matches('/')
+                                        .rsplit('/')
+                                        .next()
+                                        .unwrap_or(attachment);
+                                    col = col.push(
+                                        container(self.link_button(
+                                            label.to_owned(),
+                                            self.attachment_url(attachment),
+                                        ))
+                                        .padding([0, 0, 0, 18]),
+                                    );
+                                }
+                                col = col.push(vertical_space(15));
+                            }
+                            DetailSection::Attachments => {}
+                            DetailSection::Time => {
+                                col = col.push(
+                                    Text::new(
+                                        self.board()
+                                            .static_ins
+                                            .config
+                                            .time_format
+                                            .format(&paper.time),
+                                    )
+                                    .style(Color::new(0.5, 0.5, 0.5, 1.)),
+                                )
+                            }
+                        }
+                    }
+
+                    col
+                })
+                .id(scrollable::Id::new(DETAIL_SCROLLABLE_ID))
+                .height(Length::Fill),
+            );
 
+            if let Some(row) = actions {
                 right = right.push(row).push(vertical_space(15));
             }
         }
 
-        Split::new(
-            left,
-            Row::new()
-                .push(horizontal_space(15))
-                .push(right)
-                .push(horizontal_space(15)),
-            self.split_0_pos,
-            self.split_axis,
-            Msg::Split0Resized,
-        )
-        .into()
+        let right = Row::new()
+            .push(horizontal_space(15))
+            .push(right)
+            .push(horizontal_space(15));
+        let content: iced::Element<'_, Msg, iced::Renderer<iced::Theme>> = if self.focus_mode {
+            right.into()
+        } else {
+            Split::new(
+                left,
+                right,
+                self.split_0_pos,
+                self.split_axis,
+                Msg::Split0Resized,
+            )
+            .into()
+        };
+
+        let overlay = self.overlay.map(|overlay| match overlay {
+            Overlay::Blocklist => self.blocklist_view(),
+            Overlay::Import => self.import_view(),
+            Overlay::Export => self.export_view(),
+            Overlay::History => self.history_view(),
+            Overlay::Errors => self.errors_view(),
+            Overlay::RejectReason => self.reject_reason_view(),
+            Overlay::ConfirmSelect => self.confirm_select_view(),
+            Overlay::ConfirmAcceptAll => self.confirm_accept_all_view(),
+            Overlay::Help => self.help_view(),
+        });
+
+        Modal::new(content, overlay)
+            .backdrop(Msg::CloseOverlay)
+            .on_esc(Msg::CloseOverlay)
+            .into()
+    }
+
+    fn scale_factor(&self) -> f64 {
+        self.text_scale.into()
     }
 
     #[inline]
     fn theme(&self) -> Self::Theme {
         if self.dark_mode {
-            iced::Theme::Dark
+            self.board().static_ins.dark_theme.clone()
         } else {
-            iced::Theme::Light
+            self.board().static_ins.light_theme.clone()
         }
     }
 
     fn subscription(&self) -> iced_futures::Subscription<Self::Message> {
-        iced::subscription::events().map(Msg::Event)
+        // Events captured by widgets, e.g. typing into the search box, are not shortcuts.
+        let mut subscriptions = vec![iced::subscription::events_with(|event, status| {
+            (status == iced::event::Status::Ignored).then_some(Msg::Event(event))
+        })];
+        if self.boards.iter().any(|board| board.retry_at.is_some()) {
+            subscriptions.push(
+                iced_futures::backend::native::tokio::time::every(Duration::from_secs(1))
+                    .map(Msg::Tick),
+            );
+        }
+        if self.refreshing() && !self.board().static_ins.config.reduced_motion {
+            // Redraws the pulsing refresh indicator.
+            subscriptions.push(
+                iced_futures::backend::native::tokio::time::every(PULSE_FRAME).map(|_| Msg::Noop),
+            );
+        }
+        if !self.board().flashes.is_empty() && !self.board().static_ins.config.reduced_motion {
+            // Redraws the fading highlights.
+            subscriptions.push(
+                iced_futures::backend::native::tokio::time::every(FLASH_FRAME).map(|_| Msg::Noop),
+            );
+        }
+        // Each board cleans on its own schedule, the index telling boards with the same
+        // interval apart.
+        for (index, board) in self.boards.iter().enumerate() {
+            let secs = board.static_ins.config.auto_clean_interval_secs;
+            if secs > 0 {
+                subscriptions.push(
+                    iced_futures::backend::native::tokio::time::every(Duration::from_secs(secs))
+                        .with(index)
+                        .map(|(index, _)| Msg::CleanAccepted.on_board(index)),
+                );
+            }
+        }
+        iced::Subscription::batch(subscriptions)
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Overlay {
+    Blocklist,
+    Import,
+    Export,
+    History,
+    Errors,
+    /// Canned reasons for the papers about to be rejected.
+    RejectReason,
+    /// Confirmation of selecting many matching papers at once.
+    ConfirmSelect,
+    /// Confirmation of accepting every pending paper shown.
+    ConfirmAcceptAll,
+    /// Keyboard shortcuts, search prefixes and hook variables.
+    Help,
+}
+
+/// Progress of the last decision import.
+#[derive(Debug, Default)]
+struct ImportState {
+    path: String,
+    /// Number of decisions being applied.
+    total: usize,
+    results: Vec<(u64, bool)>,
+    /// Entries that were not applied, with the reason.
+    skipped: Vec<String>,
+}
+
+impl ImportState {
+    /// Whether decisions of the last import are still being posted.
+    fn running(&self) -> bool {
+        self.results.len() < self.total
+    }
+}
+
+/// Inputs and outcome of the export overlay.
+#[derive(Debug, Default)]
+struct ExportState {
+    path: String,
+    /// `YYYY-MM-DD`, blank for an open range.
+    from: String,
+    to: String,
+    /// Number of papers written, or why nothing was.
+    result: Option<Result<usize, String>>,
+}
+
+/// Processed papers loaded page by page into the history overlay.
+#[derive(Debug, Default)]
+struct HistoryState {
+    papers: Vec<Paper>,
+    next_page: u32,
+    loading: bool,
+    /// Set once a short page shows there is nothing further back.
+    exhausted: bool,
+}
+
 #[derive(Debug, Clone)]
 enum Msg {
+    /// A message for the board at the index, made active while the message is handled.
+    /// Messages without a board are for the active one.
+    OnBoard(usize, Box<Msg>),
+    SwitchBoard(usize),
     FontLoaded(Result<(), iced::font::Error>),
     Split0Resized(u16),
+    SaveLayout(u64),
     RefreshLoop(Duration),
     Refresh,
-    RefreshDone(Vec<Paper>),
+    RefreshDone(Result<Vec<Paper>, String>),
+    RetryNow,
+    /// A refresh due by the refresh loop, skipped while a retry is pending.
+    ScheduledRefresh,
+    SelectNewest,
+    Tick(Instant),
     OpenPaper {
         before: Option<u64>,
         target: u64,
         after: Option<u64>,
     },
+    ViewReceipt(u64),
     Accept(u64),
     Accepted(u64, bool),
+    Reject(u64),
+    /// Rejects the papers, asking for one of [`Config::reject_reasons`] first if there are any.
+    PromptReject(Vec<u64>),
+    /// Rejects the papers of the reason prompt with the reason at this index.
+    RejectWithReason(usize),
+    RejectNoteChanged(String),
+    /// Rejects the papers of the reason prompt with the typed reason.
+    RejectWithNote,
+    Rejected(u64, bool),
+    Undo(u64),
+    Undone(Paper, Result<(), String>),
     ToggleDarkMode,
     SwitchSplitAxis,
+    ToggleFocusMode,
+    /// Changes the interface zoom by a step, `None` resets it.
+    Zoom(Option<f32>),
+    /// Orders the list by a key, flipping the direction if it is already used.
+    SetSort(SortKey),
     ToggleBg,
+    ReReview(u64),
     CleanAccepted,
+    Block(u64),
+    Unblock(u64),
+    ShowOverlay(Overlay),
+    CloseOverlay,
+    ImportPathChanged(String),
+    PickImportFile,
+    ImportFilePicked(Option<PathBuf>),
+    Import,
+    Imported(u64, bool),
+    ExportPathChanged(String),
+    ExportFromChanged(String),
+    ExportToChanged(String),
+    Export,
+    LoadHistory,
+    HistoryLoaded(Result<Vec<Paper>, String>),
+    HistoryScrolled(iced::widget::scrollable::Viewport),
+    ListScrolled(iced::widget::scrollable::Viewport),
+    ToggleExpanded(u64),
+    DismissError,
+    ResetSessionStats,
+    TaskPanicked(String),
+    BackendError(String),
+    OpenLink(String),
+    /// Copies the name, email, time and info of a paper to the clipboard.
+    CopyDetails(u64),
+    Copy(String),
+    /// Writes to the address with the default mail client.
+    OpenEmail(String),
+    SearchChanged(String),
+    FocusSearch,
+    ToggleStaleOnly,
+    CycleFilter,
+    /// Selects every paper passing the search and filters, asking first above
+    /// [`Config::confirm_select_above`].
+    SelectMatching {
+        confirmed: bool,
+    },
+    /// Accepts every pending paper shown, once `confirmed`.
+    AcceptAllVisible {
+        confirmed: bool,
+    },
     Multi(Vec<Self>),
     Event(iced::Event),
+    Quit,
+    Noop,
+    FlashEnded(u64),
+}
+
+impl Msg {
+    /// `self` addressed to board `index`, unless it already names one.
+    fn on_board(self, index: usize) -> Self {
+        match self {
+            Msg::OnBoard(..) => self,
+            msg => Msg::OnBoard(index, Box::new(msg)),
+        }
+    }
+
+    /// `Accepted` or `Rejected`, depending on `decision`.
+    fn decided(paper: u64, decision: Decision, p: bool) -> Self {
+        match decision {
+            Decision::Accept => Msg::Accepted(paper, p),
+            Decision::Reject => Msg::Rejected(paper, p),
+        }
+    }
 }
 
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Deserialize, Serialize, Clone)]
 struct Paper {
+    #[serde(deserialize_with = "deserialize_pid")]
     pid: u64,
     info: String,
     time: DateTime<chrono::Utc>,
@@ -599,7 +4471,284 @@ struct Paper {
     email: Option<String>,
     #[serde(default)]
     color: Option<String>,
+    #[serde(default)]
+    priority: Option<f64>,
+    /// Page of the paper on the web board.
+    #[serde(default)]
+    url: Option<String>,
+    /// File names or urls of the files submitted with the paper.
+    #[serde(default)]
+    attachments: Vec<String>,
 
     #[serde(default)]
     processed: Option<bool>,
+    /// Whether the server confirmed `processed`, as opposed to an optimistic or failed accept.
+    #[serde(skip)]
+    confirmed: bool,
+    /// When `processed` was decided in this session.
+    #[serde(skip)]
+    processed_at: Option<DateTime<chrono::Utc>>,
+}
+
+/// Pushes `snapshot` onto `undo_stack`, dropping the oldest one beyond [`UNDO_DEPTH`].
+fn push_undo(undo_stack: &mut VecDeque<Paper>, snapshot: Paper) {
+    if undo_stack.len() >= UNDO_DEPTH {
+        undo_stack.pop_front();
+    }
+    undo_stack.push_back(snapshot);
+}
+
+/// Runs the hook of `event` on `paper` without waiting for it.
+fn run_hook(si: &'static StaticIns, event: hooks::Event, paper: Paper) -> Command<Msg> {
+    Command::perform(
+        si.config
+            .hooks
+            .run(event, si.config.board.as_deref(), paper),
+        |_| Msg::Noop,
+    )
+}
+
+/// Posts `decision` on `paper`, with a canned reject `reason` if one was picked.
+///
+/// An `idempotency_key` is sent as the `Idempotency-Key` header, so a backend honouring it
+/// applies a decision posted again, e.g. by re-running an import, only once.
+async fn post_decision(
+    si: &'static StaticIns,
+    paper: u64,
+    decision: Decision,
+    reason: Option<&str>,
+    idempotency_key: Option<&str>,
+) -> Result<(), String> {
+    let span = tracing::span!(tracing::Level::INFO, "decide paper {paper}", ?decision);
+    let _span = span.enter();
+
+    if let Some(mock) = &si.mock {
+        mock.process(paper).await;
+        return Ok(());
+    }
+
+    let url =
+        match decision {
+            Decision::Accept => &si.host.process_paper,
+            Decision::Reject => si.host.reject_paper.as_ref().ok_or_else(|| {
+                "Rejecting needs reject_paper_mapping to be configured".to_owned()
+            })?,
+        };
+    let mut request = si.client.post(url);
+    if let Some(key) = idempotency_key {
+        request = request.header("Idempotency-Key", key);
+    }
+    let request = match &si.config.decision_body {
+        Some(body) => request.json(&template::render(
+            body,
+            &template::Vars {
+                pid: paper,
+                action: match decision {
+                    Decision::Accept => "accept",
+                    Decision::Reject => "reject",
+                },
+                reason,
+                operator: si
+                    .config
+                    .operator()
+                    .or(si.config.profile.as_deref())
+                    .unwrap_or_default(),
+            },
+        )),
+        None => {
+            let mut request = request.query(&[("pid", paper)]);
+            if let Some(reason) = reason {
+                request = request.query(&[("reason", reason)]);
+            }
+            if let Some(operator) = si.config.operator() {
+                request = request.query(&[("operator", operator)]);
+            }
+            request
+        }
+    };
+    request
+        .send()
+        .map_err(request_error)
+        .and_then(|res| backend_error(si, res))
+        .await
+        .map(|_| ())
+        .inspect_err(|err| tracing::event!(tracing::Level::ERROR, "{err}"))
+}
+
+/// `err` as shown in the error banner, saying so when the request timed out,
+/// which reqwest's message leaves out.
+fn request_error(err: reqwest::Error) -> String {
+    if err.is_timeout() {
+        format!("{err}: the backend did not answer in time")
+    } else {
+        err.to_string()
+    }
+}
+
+/// Passes successful responses through, turning others into the backend's message
+/// found at [`Config::error_message_path`], or the status code when there is none.
+async fn backend_error(
+    si: &StaticIns,
+    res: reqwest::Response,
+) -> Result<reqwest::Response, String> {
+    let status = res.status();
+    if status.is_success() {
+        return Ok(res);
+    }
+    let body = res.text().await.unwrap_or_default();
+    Err(si
+        .config
+        .error_message_path
+        .as_deref()
+        .and_then(|path| {
+            let value: serde_json::Value = serde_json::from_str(&body).ok()?;
+            value.pointer(path)?.as_str().map(str::to_owned)
+        })
+        .unwrap_or_else(|| status.to_string()))
+}
+
+/// Whether the name table of `font` holds `family`, stored as UTF-16BE or as ASCII.
+fn declares_family(font: &[u8], family: &str) -> bool {
+    let utf16: Vec<u8> = family.encode_utf16().flat_map(u16::to_be_bytes).collect();
+    [family.as_bytes(), &utf16]
+        .iter()
+        .any(|needle| font.windows(needle.len()).any(|window| window == *needle))
+}
+
+/// The first line of `str` cut to `max` columns, ending in an ellipsis if anything was cut.
+///
+/// East Asian wide characters take two columns.
+fn ellipsize(str: &str, max: usize) -> String {
+    let line = str.lines().next().unwrap_or_default();
+    if line.len() == str.len() && line.width() <= max {
+        return line.to_owned();
+    }
+    let mut cut = String::new();
+    let mut width = 0;
+    for char in line.chars() {
+        width += char.width().unwrap_or_default();
+        // One column is kept for the ellipsis.
+        if width >= max {
+            break;
+        }
+        cut.push(char);
+    }
+    cut.truncate(cut.trim_end().len());
+    cut.push('…');
+    cut
+}
+
+/// Strips credentials and the query from `url`.
+fn redact_url(url: &str) -> String {
+    match reqwest::Url::parse(url) {
+        Ok(mut url) => {
+            let _ = url.set_username("");
+            let _ = url.set_password(None);
+            url.set_query(None);
+            url.to_string()
+        }
+        Err(_) => "<invalid>".to_owned(),
+    }
+}
+
+/// Runs `future`, returning the panic message instead of unwinding
+/// so a bug in a background task surfaces in the UI.
+async fn guarded<T>(future: impl std::future::Future<Output = T>) -> Result<T, String> {
+    AssertUnwindSafe(future)
+        .catch_unwind()
+        .await
+        .map_err(|panic| {
+            let message = panic
+                .downcast_ref::<&str>()
+                .map(|str| str.to_string())
+                .or_else(|| panic.downcast_ref::<String>().cloned())
+                .unwrap_or_else(|| "unknown panic".to_owned());
+            tracing::event!(tracing::Level::ERROR, "task panicked: {message}");
+            message
+        })
+}
+
+/// Accepts a pid sent either as a JSON number or as a numeric string,
+/// since backends disagree on its type.
+fn deserialize_pid<'de, D>(deserializer: D) -> Result<u64, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    struct PidVisitor;
+
+    impl<'de> serde::de::Visitor<'de> for PidVisitor {
+        type Value = u64;
+
+        fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+            formatter.write_str("a non-negative integer pid or a string containing one")
+        }
+
+        fn visit_u64<E: serde::de::Error>(self, v: u64) -> Result<Self::Value, E> {
+            Ok(v)
+        }
+
+        fn visit_i64<E: serde::de::Error>(self, v: i64) -> Result<Self::Value, E> {
+            u64::try_from(v).map_err(|_| E::custom(format!("pid {v} is negative")))
+        }
+
+        fn visit_f64<E: serde::de::Error>(self, v: f64) -> Result<Self::Value, E> {
+            Err(E::custom(format!("pid {v} is not an integer in range")))
+        }
+
+        fn visit_str<E: serde::de::Error>(self, v: &str) -> Result<Self::Value, E> {
+            v.trim()
+                .parse()
+                .map_err(|err| E::custom(format!("invalid pid {v:?}: {err}")))
+        }
+    }
+
+    deserializer.deserialize_any(PidVisitor)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::Deserialize;
+
+    #[derive(Deserialize, Debug)]
+    struct Pid {
+        #[serde(deserialize_with = "super::deserialize_pid")]
+        pid: u64,
+    }
+
+    fn pid(json: &str) -> Result<u64, serde_json::Error> {
+        serde_json::from_str::<Pid>(&format!(r#"{{"pid": {json}}}"#)).map(|e| e.pid)
+    }
+
+    #[test]
+    fn pid_from_string() {
+        assert_eq!(pid(r#""42""#).unwrap(), 42);
+        assert_eq!(pid(r#"" 42 ""#).unwrap(), 42);
+        assert!(pid(r#""4x2""#).is_err());
+    }
+
+    #[test]
+    fn pid_from_integer() {
+        assert_eq!(pid("42").unwrap(), 42);
+        assert_eq!(pid(&u64::MAX.to_string()).unwrap(), u64::MAX);
+    }
+
+    #[test]
+    fn negative_pid() {
+        assert!(pid("-1").is_err());
+        assert!(pid(r#""-1""#).is_err());
+    }
+
+    #[test]
+    fn pid_out_of_range() {
+        assert!(pid("18446744073709551616").is_err());
+        assert!(pid(r#""18446744073709551616""#).is_err());
+    }
+
+    #[test]
+    fn ellipsize_counts_wide_characters_twice() {
+        assert_eq!(super::ellipsize("hello world", 20), "hello world");
+        assert_eq!(super::ellipsize("hello world", 6), "hello…");
+        assert_eq!(super::ellipsize("日本語のタイトル", 16), "日本語のタイトル");
+        assert_eq!(super::ellipsize("日本語のタイトル", 6), "日本…");
+    }
 }
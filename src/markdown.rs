@@ -0,0 +1,130 @@
+//! Markdown in paper info, flattened into blocks of plain text.
+//!
+//! Text widgets hold a single style, so inline emphasis is dropped and links
+//! are collected per block to be rendered as buttons after it.
+
+use pulldown_cmark::{Event, HeadingLevel, Parser, Tag, TagEnd};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Kind {
+    Paragraph,
+    Heading(HeadingLevel),
+    /// A list item with its nesting depth, starting at 0.
+    Item(usize),
+    Code,
+}
+
+#[derive(Debug)]
+pub struct Block {
+    pub kind: Kind,
+    pub text: String,
+    /// Text and destination of the links in `text`.
+    pub links: Vec<(String, String)>,
+}
+
+/// Splits `str` into blocks, prefixing list items with their marker.
+pub fn parse(str: &str) -> Vec<Block> {
+    let mut blocks = vec![];
+    let mut current: Option<Block> = None;
+    // Next number of each open list, `None` for bullet lists.
+    let mut lists: Vec<Option<u64>> = vec![];
+    // Destination and start in the current text of the open link.
+    let mut link: Option<(String, usize)> = None;
+
+    fn flush(blocks: &mut Vec<Block>, current: &mut Option<Block>) {
+        if let Some(block) = current.take().filter(|block| !block.text.trim().is_empty()) {
+            blocks.push(block);
+        }
+    }
+    fn start(kind: Kind) -> Block {
+        Block {
+            kind,
+            text: String::new(),
+            links: vec![],
+        }
+    }
+
+    for event in Parser::new(str) {
+        match event {
+            // Loose list items wrap their text in a paragraph.
+            Event::Start(Tag::Paragraph)
+                if current
+                    .as_ref()
+                    .is_some_and(|block| matches!(block.kind, Kind::Item(_))) => {}
+            Event::Start(Tag::Paragraph) => {
+                flush(&mut blocks, &mut current);
+                current = Some(start(Kind::Paragraph));
+            }
+            Event::Start(Tag::Heading { level, .. }) => {
+                flush(&mut blocks, &mut current);
+                current = Some(start(Kind::Heading(level)));
+            }
+            Event::Start(Tag::CodeBlock(_)) => {
+                flush(&mut blocks, &mut current);
+                current = Some(start(Kind::Code));
+            }
+            Event::Start(Tag::List(first)) => {
+                flush(&mut blocks, &mut current);
+                lists.push(first);
+            }
+            Event::End(TagEnd::List(_)) => {
+                flush(&mut blocks, &mut current);
+                lists.pop();
+            }
+            Event::Start(Tag::Item) => {
+                flush(&mut blocks, &mut current);
+                let marker = match lists.last_mut() {
+                    Some(Some(number)) => {
+                        *number += 1;
+                        format!("{}. ", *number - 1)
+                    }
+                    _ => "• ".to_owned(),
+                };
+                current = Some(Block {
+                    text: marker,
+                    ..start(Kind::Item(lists.len().saturating_sub(1)))
+                });
+            }
+            Event::End(
+                TagEnd::Paragraph | TagEnd::Heading(_) | TagEnd::CodeBlock | TagEnd::Item,
+            ) => flush(&mut blocks, &mut current),
+            Event::Start(Tag::Link { dest_url, .. }) => {
+                let at = current.as_ref().map_or(0, |block| block.text.len());
+                link = Some((dest_url.into_string(), at));
+            }
+            Event::End(TagEnd::Link) => {
+                if let (Some((url, at)), Some(block)) = (link.take(), &mut current) {
+                    let text = block.text.get(at..).unwrap_or_default().to_owned();
+                    block
+                        .links
+                        .push((if text.is_empty() { url.clone() } else { text }, url));
+                }
+            }
+            Event::Text(text) | Event::Code(text) | Event::InlineHtml(text) | Event::Html(text) => {
+                current
+                    .get_or_insert_with(|| start(Kind::Paragraph))
+                    .text
+                    .push_str(&text);
+            }
+            Event::SoftBreak => {
+                if let Some(block) = &mut current {
+                    block.text.push(' ');
+                }
+            }
+            Event::HardBreak => {
+                if let Some(block) = &mut current {
+                    block.text.push('\n');
+                }
+            }
+            _ => (),
+        }
+    }
+    flush(&mut blocks, &mut current);
+
+    for block in &mut blocks {
+        if block.kind == Kind::Code {
+            block.text.truncate(block.text.trim_end().len());
+        }
+    }
+    blocks
+}
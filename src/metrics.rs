@@ -0,0 +1,76 @@
+//! Session counters served on localhost in the Prometheus text format, for dashboards.
+
+use std::{
+    sync::atomic::{AtomicU64, Ordering},
+    time::Duration,
+};
+
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::{TcpListener, TcpStream},
+};
+
+/// How long a client may take to send its request before it is answered anyway.
+const READ_TIMEOUT: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Default)]
+pub struct Metrics {
+    pub refreshes: AtomicU64,
+    pub refresh_failures: AtomicU64,
+    pub accepted: AtomicU64,
+    pub rejected: AtomicU64,
+    /// Pending papers currently loaded.
+    pub backlog: AtomicU64,
+}
+
+impl Metrics {
+    pub fn bump(counter: &AtomicU64) {
+        counter.fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn render(&self) -> String {
+        [
+            ("subboard_refreshes_total", &self.refreshes),
+            ("subboard_refresh_failures_total", &self.refresh_failures),
+            ("subboard_accepted_total", &self.accepted),
+            ("subboard_rejected_total", &self.rejected),
+            ("subboard_backlog", &self.backlog),
+        ]
+        .into_iter()
+        .map(|(name, value)| format!("{name} {}\n", value.load(Ordering::Relaxed)))
+        .collect()
+    }
+
+    /// Answers every request on `127.0.0.1:port` with the counters, each connection in a
+    /// task of its own so an idle client does not hold up the others.
+    pub async fn serve(&'static self, port: u16) {
+        let listener = match TcpListener::bind(("127.0.0.1", port)).await {
+            Ok(listener) => listener,
+            Err(err) => {
+                tracing::event!(tracing::Level::ERROR, "cannot serve metrics: {err}");
+                return;
+            }
+        };
+        loop {
+            let Ok((stream, _)) = listener.accept().await else {
+                continue;
+            };
+            tokio::spawn(self.answer(stream));
+        }
+    }
+
+    async fn answer(&self, mut stream: TcpStream) {
+        // Whatever was asked for, the answer is the same.
+        let mut request = [0; 1024];
+        let _ = tokio::time::timeout(READ_TIMEOUT, stream.read(&mut request)).await;
+        let body = self.render();
+        let response = format!(
+            "HTTP/1.1 200 OK\r\nContent-Type: text/plain; version=0.0.4\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+            body.len()
+        );
+        if let Err(err) = stream.write_all(response.as_bytes()).await {
+            tracing::event!(tracing::Level::WARN, "metrics request failed: {err}");
+        }
+        let _ = stream.shutdown().await;
+    }
+}
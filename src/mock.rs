@@ -0,0 +1,74 @@
+//! Offline backend serving papers from a local JSON fixture, for demos and CI.
+
+use std::{collections::HashSet, path::PathBuf, sync::Mutex, time::Duration};
+
+use serde::Deserialize;
+
+use crate::Paper;
+
+/// The `[mock]` table of the configuration file.
+#[derive(Deserialize, Debug)]
+pub struct MockConfig {
+    /// JSON file holding an array of papers, shaped like the pending endpoint's response.
+    pub fixture: PathBuf,
+    /// Simulated latency of every request.
+    #[serde(default)]
+    pub latency_ms: u64,
+}
+
+#[derive(Debug)]
+pub struct MockBackend {
+    papers: Vec<Paper>,
+    /// Pids processed during this session, no longer served as pending.
+    processed: Mutex<HashSet<u64>>,
+    latency: Duration,
+}
+
+impl MockBackend {
+    pub fn load(config: &MockConfig) -> Result<Self, String> {
+        let str = std::fs::read_to_string(&config.fixture)
+            .map_err(|err| format!("cannot read {}: {err}", config.fixture.display()))?;
+        Ok(Self {
+            papers: serde_json::from_str(&str)
+                .map_err(|err| format!("cannot parse {}: {err}", config.fixture.display()))?,
+            processed: Mutex::new(HashSet::new()),
+            latency: Duration::from_millis(config.latency_ms),
+        })
+    }
+
+    pub async fn pending(&self) -> Vec<Paper> {
+        tokio::time::sleep(self.latency).await;
+        let processed = self.processed.lock().unwrap();
+        self.papers
+            .iter()
+            .filter(|paper| !processed.contains(&paper.pid))
+            .cloned()
+            .collect()
+    }
+
+    /// Papers processed this session, served a page at a time.
+    pub async fn history(&self, page: u32, size: usize) -> Vec<Paper> {
+        tokio::time::sleep(self.latency).await;
+        let processed = self.processed.lock().unwrap();
+        self.papers
+            .iter()
+            .filter(|paper| processed.contains(&paper.pid))
+            .skip(page as usize * size)
+            .take(size)
+            .map(|paper| Paper {
+                processed: Some(true),
+                ..paper.clone()
+            })
+            .collect()
+    }
+
+    pub async fn process(&self, pid: u64) {
+        tokio::time::sleep(self.latency).await;
+        self.processed.lock().unwrap().insert(pid);
+    }
+
+    pub async fn unprocess(&self, pid: u64) {
+        tokio::time::sleep(self.latency).await;
+        self.processed.lock().unwrap().remove(&pid);
+    }
+}
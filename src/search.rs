@@ -0,0 +1,140 @@
+//! Search queries over the paper list.
+//!
+//! A query is a whitespace separated list of terms that must all match.
+//! Terms may be restricted to a field with a prefix:
+//!
+//! - `name:alice`
+//! - `email:gmail.com`
+//! - `info:hello`
+//! - `color:ff0000`
+//!
+//! Bare terms match the name, the email or the info. Matching is a case-insensitive
+//! substring match.
+
+use crate::Paper;
+
+/// Field prefixes a term may start with, with what they match.
+pub const PREFIXES: [(&str, &str); 4] = [
+    ("name:", "The author's name"),
+    ("email:", "The author's email"),
+    ("info:", "The paper's info"),
+    ("color:", "The paper's color in hex, # optional"),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Field {
+    Name,
+    Email,
+    Info,
+    Color,
+    /// Name, email or info.
+    Any,
+}
+
+/// A parsed search query.
+#[derive(Debug, Default)]
+pub struct Query {
+    terms: Vec<(Field, String)>,
+}
+
+impl Query {
+    pub fn parse(str: &str) -> Self {
+        Self {
+            terms: str
+                .split_whitespace()
+                .map(|term| {
+                    let (field, value) = match term.split_once(':') {
+                        Some(("name", value)) => (Field::Name, value),
+                        Some(("email", value)) => (Field::Email, value),
+                        Some(("info", value)) => (Field::Info, value),
+                        Some(("color", value)) => (Field::Color, value.trim_start_matches('#')),
+                        _ => (Field::Any, term),
+                    };
+                    (field, value.to_lowercase())
+                })
+                .filter(|(_, value)| !value.is_empty())
+                .collect(),
+        }
+    }
+
+    pub fn matches(&self, paper: &Paper) -> bool {
+        let contains = |haystack: &str, needle: &str| haystack.to_lowercase().contains(needle);
+        self.terms.iter().all(|(field, value)| match field {
+            Field::Name => contains(&paper.name, value),
+            Field::Email => paper.email.as_deref().is_some_and(|e| contains(e, value)),
+            Field::Info => contains(&paper.info, value),
+            Field::Color => paper.color.as_deref().is_some_and(|e| contains(e, value)),
+            Field::Any => {
+                contains(&paper.name, value)
+                    || paper.email.as_deref().is_some_and(|e| contains(e, value))
+                    || contains(&paper.info, value)
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn paper() -> Paper {
+        Paper {
+            pid: 1,
+            info: "Hello World".to_owned(),
+            time: chrono::Utc::now(),
+            name: "Alice".to_owned(),
+            email: Some("alice@gmail.com".to_owned()),
+            color: Some("FF0000".to_owned()),
+            priority: None,
+            url: None,
+            attachments: vec![],
+            processed: None,
+            confirmed: false,
+            processed_at: None,
+        }
+    }
+
+    #[test]
+    fn parses_prefixes_and_bare_terms() {
+        let query = Query::parse("name:Alice  email:gmail color:#ff0000 info:hi world");
+        assert_eq!(
+            query.terms,
+            [
+                (Field::Name, "alice".to_owned()),
+                (Field::Email, "gmail".to_owned()),
+                (Field::Color, "ff0000".to_owned()),
+                (Field::Info, "hi".to_owned()),
+                (Field::Any, "world".to_owned()),
+            ]
+        );
+    }
+
+    #[test]
+    fn unknown_prefixes_are_bare_terms() {
+        let query = Query::parse("pid:1 Name:bob");
+        assert_eq!(
+            query.terms,
+            [
+                (Field::Any, "pid:1".to_owned()),
+                (Field::Any, "name:bob".to_owned()),
+            ]
+        );
+    }
+
+    #[test]
+    fn empty_terms_are_dropped() {
+        assert!(Query::parse("  name: color:#  ").terms.is_empty());
+        assert!(Query::parse("").matches(&paper()));
+    }
+
+    #[test]
+    fn matches_case_insensitively() {
+        let paper = paper();
+        assert!(Query::parse("ALICE").matches(&paper));
+        assert!(Query::parse("world").matches(&paper));
+        assert!(Query::parse("color:ff0000").matches(&paper));
+        assert!(Query::parse("name:alice email:GMAIL").matches(&paper));
+        assert!(!Query::parse("name:alice email:yahoo").matches(&paper));
+        assert!(!Query::parse("info:alice").matches(&paper));
+    }
+}
@@ -0,0 +1,91 @@
+//! Local state persisted between runs, next to the configuration file.
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fs,
+    path::Path,
+};
+
+use serde::{Deserialize, Deserializer, Serialize};
+
+use crate::SortKey;
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct State {
+    /// Pids that are filtered out of every refresh, by the name of their board, empty
+    /// without boards.
+    #[serde(default, deserialize_with = "deserialize_blocked")]
+    pub blocked: BTreeMap<String, BTreeSet<u64>>,
+    /// Pids opened on each board, by board name like `blocked`.
+    #[serde(default)]
+    pub seen: BTreeMap<String, BTreeSet<u64>>,
+    /// Position of the divider between the paper list and the detail pane.
+    #[serde(default)]
+    pub split_pos: Option<u16>,
+    /// Whether the panes are stacked instead of side by side.
+    #[serde(default)]
+    pub split_horizontal: bool,
+    #[serde(default)]
+    pub dark_mode: bool,
+    #[serde(default)]
+    pub window_size: Option<(u32, u32)>,
+    #[serde(default)]
+    pub text_scale: Option<f32>,
+    #[serde(default)]
+    pub sort: SortKey,
+    #[serde(default)]
+    pub sort_ascending: bool,
+}
+
+impl State {
+    pub fn is_blocked(&self, board: &str, pid: u64) -> bool {
+        self.blocked
+            .get(board)
+            .is_some_and(|pids| pids.contains(&pid))
+    }
+
+    pub fn is_seen(&self, board: &str, pid: u64) -> bool {
+        self.seen.get(board).is_some_and(|pids| pids.contains(&pid))
+    }
+
+    /// Loads the state file, falling back to defaults if it is absent or corrupt.
+    pub fn load(path: &Path) -> Self {
+        match fs::read_to_string(path) {
+            Ok(str) => toml::from_str(&str).unwrap_or_else(|err| {
+                tracing::event!(tracing::Level::WARN, "ignoring corrupt state file: {err}");
+                Default::default()
+            }),
+            Err(_) => Default::default(),
+        }
+    }
+
+    /// Writes the state file, logging failures.
+    pub fn save(&self, path: &Path) {
+        let result = toml::to_string(self)
+            .map_err(|err| err.to_string())
+            .and_then(|str| fs::write(path, str).map_err(|err| err.to_string()));
+        if let Err(err) = result {
+            tracing::event!(tracing::Level::ERROR, "failed to save state: {err}");
+        }
+    }
+}
+
+/// Reads blocked pids by board, or the bare list of files written before boards, which
+/// belongs to the single unnamed board.
+fn deserialize_blocked<'de, D>(deserializer: D) -> Result<BTreeMap<String, BTreeSet<u64>>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Blocked {
+        ByBoard(BTreeMap<String, BTreeSet<u64>>),
+        Unnamed(BTreeSet<u64>),
+    }
+
+    Ok(match Blocked::deserialize(deserializer)? {
+        Blocked::ByBoard(blocked) => blocked,
+        Blocked::Unnamed(pids) if pids.is_empty() => BTreeMap::new(),
+        Blocked::Unnamed(pids) => BTreeMap::from([(String::new(), pids)]),
+    })
+}
@@ -0,0 +1,123 @@
+//! Custom widget styles.
+
+use hex_color::HexColor;
+use iced::{
+    theme,
+    widget::{button, text_input},
+    Color,
+};
+
+pub fn from_hex(color: HexColor) -> Color {
+    Color::from_rgba8(color.r, color.g, color.b, color.a as f32 / 255.0)
+}
+
+/// A container style filled with a single color.
+pub fn filled(background: Color, text_color: Option<Color>) -> theme::Container {
+    theme::Container::Custom(Box::new(move |_: &_| iced::widget::container::Appearance {
+        text_color,
+        background: Some(iced::Background::Color(background)),
+        border_radius: Default::default(),
+        border_width: 0.,
+        border_color: Default::default(),
+    }))
+}
+
+/// A selected row in the paper list, filled with `background` or the theme's box color
+/// and tinted with the translucent `flash` of a decision.
+///
+/// The row holding keyboard focus gets a ring in the primary color.
+pub fn selected_row(
+    background: Option<Color>,
+    focused: bool,
+    flash: Option<Color>,
+) -> theme::Container {
+    theme::Container::Custom(Box::new(move |theme: &iced::Theme| {
+        let mut appearance =
+            iced::widget::container::StyleSheet::appearance(theme, &theme::Container::Box);
+        if let Some(color) = background {
+            appearance.background = Some(iced::Background::Color(color));
+        }
+        if let Some(flash) = flash {
+            appearance.background = Some(iced::Background::Color(match appearance.background {
+                Some(iced::Background::Color(under)) => over(under, flash),
+                _ => flash,
+            }));
+        }
+        if focused {
+            appearance.border_width = 1.5;
+            appearance.border_color = theme.palette().primary;
+        }
+        appearance
+    }))
+}
+
+/// `top` painted over the opaque `under`.
+fn over(under: Color, top: Color) -> Color {
+    let mix = |from: f32, to: f32| from + (to - from) * top.a;
+    Color {
+        r: mix(under.r, top.r),
+        g: mix(under.g, top.g),
+        b: mix(under.b, top.b),
+        a: under.a,
+    }
+}
+
+/// A primary button filled with `background` instead of the theme's color.
+pub fn filled_button(background: Color) -> theme::Button {
+    theme::Button::Custom(Box::new(FilledButton(background)))
+}
+
+struct FilledButton(Color);
+
+impl button::StyleSheet for FilledButton {
+    type Style = iced::Theme;
+
+    fn active(&self, style: &Self::Style) -> button::Appearance {
+        button::Appearance {
+            background: Some(iced::Background::Color(self.0)),
+            ..style.active(&theme::Button::Primary)
+        }
+    }
+}
+
+/// Default text input with a thicker border while focused.
+pub struct FocusRing;
+
+impl text_input::StyleSheet for FocusRing {
+    type Style = iced::Theme;
+
+    fn active(&self, style: &Self::Style) -> text_input::Appearance {
+        style.active(&theme::TextInput::Default)
+    }
+
+    fn focused(&self, style: &Self::Style) -> text_input::Appearance {
+        text_input::Appearance {
+            border_width: 2.,
+            ..style.focused(&theme::TextInput::Default)
+        }
+    }
+
+    fn placeholder_color(&self, style: &Self::Style) -> Color {
+        style.placeholder_color(&theme::TextInput::Default)
+    }
+
+    fn value_color(&self, style: &Self::Style) -> Color {
+        style.value_color(&theme::TextInput::Default)
+    }
+
+    fn disabled_color(&self, style: &Self::Style) -> Color {
+        style.disabled_color(&theme::TextInput::Default)
+    }
+
+    fn selection_color(&self, style: &Self::Style) -> Color {
+        style.selection_color(&theme::TextInput::Default)
+    }
+
+    fn hovered(&self, style: &Self::Style) -> text_input::Appearance {
+        style.hovered(&theme::TextInput::Default)
+    }
+
+    fn disabled(&self, style: &Self::Style) -> text_input::Appearance {
+        style.disabled(&theme::TextInput::Default)
+    }
+}
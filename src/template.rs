@@ -0,0 +1,140 @@
+//! The configurable body of decision requests, for backends expecting their own shape.
+//!
+//! Strings of the template may hold `{pid}`, `{action}`, `{reason}` and
+//! `{operator}`. A string that is a single placeholder takes the value's type,
+//! so `"{pid}"` becomes a number and a missing `{reason}` becomes `null`.
+
+use serde_json::Value;
+
+const PLACEHOLDERS: [&str; 4] = ["pid", "action", "reason", "operator"];
+
+/// Values filled into a template.
+pub struct Vars<'a> {
+    pub pid: u64,
+    /// `accept` or `reject`.
+    pub action: &'a str,
+    pub reason: Option<&'a str>,
+    pub operator: &'a str,
+}
+
+impl Vars<'_> {
+    fn get(&self, name: &str) -> Value {
+        match name {
+            "pid" => self.pid.into(),
+            "action" => self.action.into(),
+            "reason" => self.reason.map_or(Value::Null, Value::from),
+            "operator" => self.operator.into(),
+            _ => unreachable!("placeholders are validated at startup"),
+        }
+    }
+}
+
+/// Checks that every placeholder in `template` is known and closed.
+pub fn validate(template: &Value) -> Result<(), String> {
+    match template {
+        Value::String(str) => placeholders(str).map(|_| ()),
+        Value::Array(values) => values.iter().try_for_each(validate),
+        Value::Object(map) => map.values().try_for_each(validate),
+        _ => Ok(()),
+    }
+}
+
+/// `template` with its placeholders replaced by `vars`.
+pub fn render(template: &Value, vars: &Vars) -> Value {
+    match template {
+        Value::String(str) => {
+            let names = placeholders(str).unwrap_or_default();
+            if let [name] = names.as_slice() {
+                if str.len() == name.len() + 2 {
+                    return vars.get(name);
+                }
+            }
+            let mut str = str.clone();
+            for name in names {
+                let value = match vars.get(name) {
+                    Value::String(value) => value,
+                    Value::Null => String::new(),
+                    value => value.to_string(),
+                };
+                str = str.replace(&format!("{{{name}}}"), &value);
+            }
+            Value::String(str)
+        }
+        Value::Array(values) => Value::Array(values.iter().map(|e| render(e, vars)).collect()),
+        Value::Object(map) => Value::Object(
+            map.iter()
+                .map(|(key, value)| (key.clone(), render(value, vars)))
+                .collect(),
+        ),
+        value => value.clone(),
+    }
+}
+
+/// Names of the placeholders in `str`, in order.
+fn placeholders(str: &str) -> Result<Vec<&str>, String> {
+    let mut names = vec![];
+    let mut rest = str;
+    while let Some(start) = rest.find('{') {
+        let Some(len) = rest[start..].find('}') else {
+            return Err(format!("unclosed placeholder in {str:?}"));
+        };
+        let name = &rest[start + 1..start + len];
+        if !PLACEHOLDERS.contains(&name) {
+            return Err(format!(
+                "unknown placeholder {{{name}}} in {str:?}, expected one of {}",
+                PLACEHOLDERS.map(|name| format!("{{{name}}}")).join(", ")
+            ));
+        }
+        names.push(name);
+        rest = &rest[start + len + 1..];
+    }
+    Ok(names)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn vars() -> Vars<'static> {
+        Vars {
+            pid: 42,
+            action: "reject",
+            reason: None,
+            operator: "alice",
+        }
+    }
+
+    #[test]
+    fn rejects_unknown_placeholders() {
+        let err = validate(&json!({"by": ["{user}"]})).unwrap_err();
+        assert!(err.contains("unknown placeholder {user}"), "{err}");
+    }
+
+    #[test]
+    fn rejects_unclosed_braces() {
+        let err = validate(&json!({"pid": "{pid"})).unwrap_err();
+        assert!(err.contains("unclosed placeholder"), "{err}");
+    }
+
+    #[test]
+    fn substitutes_placeholders() {
+        let template = json!({
+            "id": "{pid}",
+            "note": "{action} #{pid} by {operator}: {reason}",
+            "reason": "{reason}",
+            "fixed": [true, 1],
+        });
+        assert_eq!(validate(&template), Ok(()));
+        assert_eq!(
+            render(&template, &vars()),
+            json!({
+                "id": 42,
+                "note": "reject #42 by alice: ",
+                "reason": null,
+                "fixed": [true, 1],
+            })
+        );
+    }
+}